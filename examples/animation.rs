@@ -0,0 +1,36 @@
+use rui::*;
+
+fn main() {
+    state(
+        || false,
+        |expanded, cx| {
+            let (size, color) = if cx[expanded] {
+                ([300.0, 120.0], AZURE_HIGHLIGHT)
+            } else {
+                ([120.0, 120.0], RED_HIGHLIGHT)
+            };
+            vstack((
+                hstack((
+                    button("Spring", move |cx| {
+                        with_animation(cx, Animation::spring(170.0, 12.0), |cx| {
+                            cx[expanded] = !cx[expanded]
+                        })
+                    }),
+                    button("Ease", move |cx| {
+                        with_animation(cx, Animation::ease_in_out(0.4), |cx| {
+                            cx[expanded] = !cx[expanded]
+                        })
+                    }),
+                    button("Jump", move |cx| cx[expanded] = !cx[expanded]),
+                ))
+                .padding(Auto),
+                rectangle()
+                    .corner_radius(10.0)
+                    .color(color)
+                    .size(size)
+                    .padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
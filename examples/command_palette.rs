@@ -0,0 +1,28 @@
+use rui::*;
+
+fn main() {
+    let primary_shift = KeyboardModifiers {
+        shift: true,
+        ..KeyboardModifiers::primary()
+    };
+
+    state(
+        || false,
+        move |open, _| {
+            vstack((
+                "Press Ctrl/Cmd+Shift+P to open the command palette".padding(Auto),
+                command_palette(open).padding(Auto),
+            ))
+            .command("File:New", Some(HotKey::KeyN), |_| println!("new"))
+            .command("File:Open", Some(HotKey::KeyO), |_| println!("open"))
+            .command("File:Save", Some(HotKey::KeyS), |_| println!("save"))
+            .command("Edit:Undo", Some(HotKey::KeyZ), |_| println!("undo"))
+            .command(
+                "View:Command Palette",
+                Some(HotKey::KeyP.with_modifiers(primary_shift)),
+                move |cx| cx[open] = true,
+            )
+        },
+    )
+    .run()
+}
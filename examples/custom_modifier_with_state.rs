@@ -19,7 +19,10 @@ trait MyMod: View + Sized {
             self.clone()
                 .offset(LocalOffset::new(0.0, cx[s].offset))
                 .anim(move |cx, _| {
-                    cx[s].offset *= 0.9;
+                    if cx[s].offset.abs() > 0.01 {
+                        cx[s].offset *= 0.9;
+                        cx.request_frame();
+                    }
                 })
         })
     }
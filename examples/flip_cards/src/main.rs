@@ -17,25 +17,64 @@ enum Action {
     ToggleAnswer,
 }
 
-#[derive(Default)]
 struct FlipCardAnimation {
-    entrance_offset: Option<LocalOffset>,
-    entrance_velocity: Option<LocalOffset>,
+    entrance_offset: Animated<LocalOffset>,
+    /// Where the finger has dragged the card to.
     offset: Option<LocalOffset>,
-    animated_offset: Option<LocalOffset>,
-    animated_offset_velocity: Option<LocalOffset>,
+    /// Follows the finger while dragging, then springs to where the
+    /// action sends the card.
+    animated_offset: Animated<LocalOffset>,
     action: Option<Action>,
     show_answer: bool,
     card_id: usize,
 }
 
+/// Lags a little behind the finger, without overshooting.
+const FOLLOW: Animation = Animation::Spring {
+    stiffness: 100.0,
+    damping: 20.0,
+};
+
+/// Throws the card, bouncing as it comes back.
+const THROW: Animation = Animation::Spring {
+    stiffness: 105.0,
+    damping: 10.0,
+};
+
 impl FlipCardAnimation {
     fn new() -> Self {
+        // Spring in from below.
+        let mut entrance_offset = Animated::new(LocalOffset::new(0.0, 1000.0));
+        entrance_offset.set(LocalOffset::zero(), Some(Animation::spring(95.0, 11.0)));
         Self {
-            entrance_offset: Some(LocalOffset::new(0.0, 1000.0)),
-            ..Default::default()
+            entrance_offset,
+            offset: None,
+            animated_offset: Animated::new(LocalOffset::zero()),
+            action: None,
+            show_answer: false,
+            card_id: 0,
         }
     }
+
+    /// Springs the card to where the action sends it, or back to the middle.
+    fn throw(&mut self) {
+        let target = match self.action {
+            Some(Action::PreviousCard) => LocalOffset::new(-1400.0, 0.0),
+            Some(Action::NextCard) => LocalOffset::new(1400.0, 0.0),
+            Some(Action::ToggleAnswer) => LocalOffset::new(0.0, 200.0),
+            None => LocalOffset::zero(),
+        };
+        self.animated_offset.set(target, Some(THROW));
+    }
+
+    /// Brings in the card `step` away from this one from the other side.
+    fn switch_card(&mut self, step: usize, count: usize, from: LocalOffset) {
+        self.show_answer = false;
+        self.action = None;
+        self.card_id = (self.card_id + step) % count;
+        self.animated_offset = Animated::new(from);
+        self.throw();
+    }
 }
 
 fn flip_cards_view(v: Vec<FlipCard>) -> impl View {
@@ -86,26 +125,25 @@ fn flip_cards_view(v: Vec<FlipCard>) -> impl View {
                 spacer(),
             )),
         ))
-        .offset(
-            cx[a].animated_offset.unwrap_or_default() + cx[a].entrance_offset.unwrap_or_default(),
-        )
+        .offset(cx[a].animated_offset.value() + cx[a].entrance_offset.value())
         .size([350.0, 200.0])
         .drag(move |cx, offset, state, _| {
             // Handle drag events
             match state {
                 GestureState::Began => {
-                    cx[a].offset = Some(offset + cx[a].animated_offset.unwrap_or_default());
+                    cx[a].offset = Some(offset + cx[a].animated_offset.value());
                 }
                 GestureState::Changed => {
-                    cx[a].offset = cx[a].offset.map(|o| o + offset);
+                    if let Some(o) = cx[a].offset {
+                        cx[a].offset = Some(o + offset);
+                        cx[a].animated_offset.set(o + offset, Some(FOLLOW));
+                    }
                 }
                 GestureState::Ended => {
+                    let velocity = cx[a].animated_offset.velocity();
                     if let Some(cx_offset) = cx[a].offset {
                         let is_horizontal = cx_offset.y.abs() < cx_offset.x.abs();
-                        if is_horizontal
-                            && (cx_offset.x.abs() > 200.0
-                                || cx[a].animated_offset_velocity.unwrap_or_default().x.abs()
-                                    > 100.0)
+                        if is_horizontal && (cx_offset.x.abs() > 200.0 || velocity.x.abs() > 100.0)
                         {
                             cx[a].action = Some(if cx_offset.x > 0.0 {
                                 Action::NextCard
@@ -118,99 +156,50 @@ fn flip_cards_view(v: Vec<FlipCard>) -> impl View {
                     cx[a].offset = None;
 
                     // On tap, toggle the answer visibility
-                    if cx[a].animated_offset_velocity.unwrap_or_default().x.abs() < 10.0
-                        && cx[a].animated_offset_velocity.unwrap_or_default().y.abs() < 10.0
-                    {
+                    if velocity.x.abs() < 10.0 && velocity.y.abs() < 10.0 {
                         cx[a].action = Some(Action::ToggleAnswer);
                     }
+                    cx[a].throw();
                 }
             }
         })
         .anim(move |cx, dt| {
-            // Animate cx[a].entrance_offset towards zero using a spring animation
-            let target_offset = LocalOffset::zero();
-            let diff = target_offset - cx[a].entrance_offset.unwrap_or_default();
-            let speed = 95.0;
-            let damping = 11.0;
-            let new_velocity = cx[a].entrance_velocity.unwrap_or_default() + diff * speed * dt
-                - cx[a].entrance_velocity.unwrap_or_default() * damping * dt;
-            cx[a].entrance_velocity = Some(new_velocity);
-            let new_offset = cx[a].entrance_offset.unwrap_or_default()
-                + cx[a].entrance_velocity.unwrap_or_default() * dt;
-            cx[a].entrance_offset = Some(new_offset);
-
-            // Animate animated_offset towards offset
-            if let Some(offset) = cx[a].offset {
-                let target_offset = offset;
-                let animated_offset = cx[a].animated_offset.unwrap_or_default();
-                let diff = target_offset - animated_offset;
-                let new_velocity = diff * 10.0;
-                cx[a].animated_offset_velocity = Some(new_velocity);
-                let new_offset =
-                    animated_offset + cx[a].animated_offset_velocity.unwrap_or_default() * dt;
-                cx[a].animated_offset = Some(new_offset);
-            }
-
-            // Continue using the velocity to animate the offset
-            // if the user is not dragging the card
-            let target_offset = if let Some(exit) = &cx[a].action {
-                match exit {
-                    Action::PreviousCard => LocalOffset::new(-1400.0, 0.0),
-                    Action::NextCard => LocalOffset::new(1400.0, 0.0),
-                    Action::ToggleAnswer => LocalOffset::new(0.0, 200.0),
-                }
-            } else {
-                LocalOffset::zero()
-            };
-
-            let diff = target_offset - cx[a].animated_offset.unwrap_or_default();
+            let entering = cx[a].entrance_offset.step(dt);
+            let moving = cx[a].animated_offset.step(dt);
 
-            let is_horizontal = cx[a].animated_offset.unwrap_or_default().y.abs()
-                < cx[a].animated_offset.unwrap_or_default().x.abs();
+            let offset = cx[a].animated_offset.value();
+            let is_horizontal = offset.y.abs() < offset.x.abs();
 
             // If the x offset is far enough, perform the horizontal swipe action
-            if is_horizontal && cx[a].animated_offset.unwrap_or_default().x.abs() > 1000.0 {
+            if is_horizontal && offset.x.abs() > 1000.0 {
                 match &cx[a].action {
                     Some(Action::PreviousCard) => {
-                        cx[a].show_answer = false;
-                        cx[a].action = None;
-                        cx[a].card_id = (cx[a].card_id + flip_cards_count - 1) % flip_cards_count;
-                        cx[a].animated_offset = Some(LocalOffset::new(500.0, 0.0));
+                        cx[a].switch_card(
+                            flip_cards_count - 1,
+                            flip_cards_count,
+                            LocalOffset::new(500.0, 0.0),
+                        );
                     }
                     Some(Action::NextCard) => {
-                        cx[a].show_answer = false;
-                        cx[a].action = None;
-                        cx[a].card_id = (cx[a].card_id + 1) % flip_cards_count;
-                        cx[a].animated_offset = Some(LocalOffset::new(-500.0, 0.0));
+                        cx[a].switch_card(1, flip_cards_count, LocalOffset::new(-500.0, 0.0));
                     }
                     _ => {}
                 }
             }
 
             // Perform the show answer action
-            if !is_horizontal && cx[a].animated_offset.unwrap_or_default().y > 40.0 {
-                match &cx[a].action {
-                    Some(Action::ToggleAnswer) => {
-                        cx[a].show_answer = !cx[a].show_answer;
-                        cx[a].action = None;
-                    }
-                    _ => {}
+            if !is_horizontal && offset.y > 40.0 {
+                if let Some(Action::ToggleAnswer) = &cx[a].action {
+                    cx[a].show_answer = !cx[a].show_answer;
+                    cx[a].action = None;
+                    cx[a].throw();
                 }
             }
 
-            let speed = 105.0;
-            let new_velocity =
-                cx[a].animated_offset_velocity.unwrap_or_default() + diff * speed * dt;
-            cx[a].animated_offset_velocity = Some(new_velocity);
-
-            // Decay the velocity
-            let decay_speed = 10.0;
-            cx[a].animated_offset_velocity = cx[a]
-                .animated_offset_velocity
-                .map(|v| v * (1.0 - decay_speed * dt));
-            cx[a].animated_offset = cx[a]
-                .animated_offset
-                .map(|o| o + cx[a].animated_offset_velocity.unwrap_or_default() * dt);
+            // Keep going until the card comes to rest.
+            if entering || moving || cx[a].offset.is_some() {
+                cx.request_frame();
+            }
         })
     })
 }
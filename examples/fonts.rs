@@ -0,0 +1,39 @@
+use rui::*;
+
+// Pass font files to use them, for example:
+// cargo run --example fonts -- /usr/share/fonts/truetype/dejavu/DejaVuSans.ttf
+fn main() {
+    let mut families = vec![];
+    for path in std::env::args().skip(1) {
+        match register_font_file(&path) {
+            Ok(family) => families.push(family),
+            Err(err) => eprintln!("{}: {}", path, err),
+        }
+    }
+    let family = families.first().cloned().unwrap_or_default();
+
+    state(
+        || 0.44,
+        move |freq, cx| {
+            vstack((
+                text("The quick brown fox").padding(Auto),
+                text("jumps over the lazy dog")
+                    .font_weight(FontWeight::BOLD)
+                    .italic()
+                    .padding(Auto),
+                rich_text(
+                    AttributedString::new()
+                        .span(span("Proportional text, "))
+                        .span(span("and monospace").monospaced()),
+                )
+                .padding(Auto),
+                format!("{:8.2} Hz", cx[freq] * 1000.0)
+                    .monospaced()
+                    .padding(Auto),
+                hslider(freq).padding(Auto),
+            ))
+            .env(Font::new(family.clone()))
+        },
+    )
+    .run()
+}
@@ -43,6 +43,7 @@ fn main() {
                     let mut v = cx[s].animated;
                     if anim_to(&mut v, cx[s].dragged) {
                         cx[s].animated = v;
+                        cx.request_frame();
                     }
                 })
                 .padding(Auto)
@@ -5,6 +5,24 @@ struct MyState {
     x: f32,
 }
 
+/// A knob with a pointer which turns with the value, from lower left
+/// to lower right.
+fn pointer_knob(value: impl Binding<f32>, cx: &Context) -> impl View {
+    let angle = (0.5 - *value.get(cx)) * 1.5 * std::f32::consts::PI;
+    zstack((
+        circle().color(CONTROL_BACKGROUND),
+        vstack((
+            rectangle().color(AZURE_HIGHLIGHT).size([4.0, 16.0]),
+            spacer(),
+        ))
+        .rotate(angle, [0.5, 0.5]),
+    ))
+    .size([80.0, 80.0])
+    .drag_s(value, move |v, delta, _, _| {
+        *v = (*v + (delta.x + delta.y) / 400.0).clamp(0.0, 1.0)
+    })
+}
+
 fn main() {
     state(MyState::default, |state, cx| {
         vstack((
@@ -12,7 +30,7 @@ fn main() {
             map(
                 cx[state].x * 0.01,
                 move |v, cx| cx[state].x = v * 100.0,
-                |s, _| knob(s).padding(Auto),
+                |s, cx| hstack((knob(s).padding(Auto), pointer_knob(s, cx).padding(Auto))),
             ),
         ))
     })
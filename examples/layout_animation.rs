@@ -0,0 +1,59 @@
+use rui::*;
+
+const COLORS: [vger::color::Color; 4] = [
+    AZURE_HIGHLIGHT,
+    RED_HIGHLIGHT,
+    CONTROL_BACKGROUND,
+    MEDIUM_GRAY,
+];
+
+fn main() {
+    state(
+        || (vec![0, 1, 2, 3], None),
+        |s, cx| {
+            let (clips, selected) = cx[s].clone();
+            vstack((
+                button("Rotate", move |cx| cx[s].0.rotate_left(1)).padding(Auto),
+                hlist(clips, move |&clip| {
+                    // The selected clip leaves a gap, and moves to the detail view.
+                    let thumbnail = if selected == Some(clip) {
+                        any_view(EmptyView {})
+                    } else {
+                        any_view(
+                            rectangle()
+                                .corner_radius(5.0)
+                                .color(COLORS[clip])
+                                .matched_geometry(clip, "clips")
+                                .tap(move |cx| {
+                                    with_animation(cx, Animation::spring(170.0, 20.0), |cx| {
+                                        cx[s].1 = Some(clip)
+                                    })
+                                }),
+                        )
+                    };
+                    thumbnail
+                        .size([40.0, 40.0])
+                        .padding(Auto)
+                        .animate_layout(Animation::spring(170.0, 20.0))
+                }),
+                match selected {
+                    Some(clip) => any_view(
+                        rectangle()
+                            .corner_radius(10.0)
+                            .color(COLORS[clip])
+                            .matched_geometry(clip, "clips")
+                            .size([200.0, 200.0])
+                            .tap(move |cx| {
+                                with_animation(cx, Animation::spring(170.0, 20.0), |cx| {
+                                    cx[s].1 = None
+                                })
+                            })
+                            .padding(Auto),
+                    ),
+                    None => any_view(EmptyView {}),
+                },
+            ))
+        },
+    )
+    .run()
+}
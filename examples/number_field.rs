@@ -0,0 +1,39 @@
+use rui::*;
+
+fn main() {
+    vstack((
+        state(
+            || 440.0,
+            |freq, _| {
+                number_field(freq)
+                    .unit(Unit::Hertz)
+                    .range(20.0..=20000.0)
+                    .step(10.0)
+                    .size([150.0, 30.0])
+                    .padding(Auto)
+            },
+        ),
+        state(
+            || -6.0,
+            |gain, _| {
+                number_field(gain)
+                    .unit(Unit::Decibels)
+                    .range(-60.0..=12.0)
+                    .size([150.0, 30.0])
+                    .padding(Auto)
+            },
+        ),
+        state(
+            || 250.0,
+            |time, _| {
+                number_field(time)
+                    .unit(Unit::Milliseconds)
+                    .range(0.0..=5000.0)
+                    .step(5.0)
+                    .size([150.0, 30.0])
+                    .padding(Auto)
+            },
+        ),
+    ))
+    .run()
+}
@@ -0,0 +1,24 @@
+use rui::*;
+
+fn overlapping() -> impl View {
+    zstack((
+        circle().color(RED_HIGHLIGHT).offset([-20.0, 0.0]),
+        circle().color(AZURE_HIGHLIGHT).offset([20.0, 0.0]),
+    ))
+    .size([120.0, 80.0])
+}
+
+fn main() {
+    state(
+        || 0.5,
+        |alpha, cx| {
+            let a = cx[alpha];
+            vstack((
+                // The red circle shows through the blue one.
+                overlapping().opacity(a).padding(Auto),
+                hslider(alpha).padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
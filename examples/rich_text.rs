@@ -0,0 +1,30 @@
+use rui::*;
+
+fn main() {
+    state(
+        || 0,
+        |clicks, cx| {
+            let text = AttributedString::new()
+                .span(span("Rich text ").font_size(30).bold())
+                .span(span("mixes "))
+                .span(span("colors").color(AZURE_HIGHLIGHT))
+                .span(span(", "))
+                .span(span("highlights").background(RED_HIGHLIGHT_DARK))
+                .span(span(", "))
+                .span(span("underlines").underline())
+                .span(span(" and "))
+                .span(span("strikethrough").strikethrough())
+                .span(span(" in one paragraph which wraps as a whole. "))
+                .span(
+                    span("This link")
+                        .color(AZURE_HIGHLIGHT)
+                        .underline()
+                        .on_tap(move |cx| cx[clicks] += 1),
+                )
+                .span(span(&format!(" was clicked {} times.", cx[clicks])));
+
+            rich_text(text).max_width(400.0).padding(Auto)
+        },
+    )
+    .run()
+}
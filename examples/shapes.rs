@@ -1,12 +1,54 @@
 use rui::*;
 
 fn main() {
-    hstack((
-        circle().color(RED_HIGHLIGHT).padding(Auto),
-        rectangle()
-            .corner_radius(5.0)
-            .color(AZURE_HIGHLIGHT)
-            .padding(Auto),
+    vstack((
+        hstack((
+            circle().color(RED_HIGHLIGHT).padding(Auto),
+            rectangle()
+                .corner_radius(5.0)
+                .color(AZURE_HIGHLIGHT)
+                .padding(Auto),
+        )),
+        hstack((
+            circle()
+                .fill(RED_HIGHLIGHT)
+                .stroke(WHITE, 4.0)
+                .padding(Auto),
+            rectangle()
+                .corner_radius(10.0)
+                .stroke(AZURE_HIGHLIGHT, 2.0)
+                .dash(8.0, 4.0)
+                .padding(Auto),
+            "card"
+                .padding(Auto)
+                .background(rectangle().corner_radius(10.0).color(CONTROL_BACKGROUND))
+                .border(MEDIUM_GRAY, 1.0)
+                .corner_radius(10.0)
+                .shadow(BLACK.alpha(0.5), 8.0, [0.0, -4.0])
+                .corner_radius(10.0)
+                .padding(Auto),
+        )),
+        hstack((
+            ellipse().color(GREEN_HIGHLIGHT).padding(Auto),
+            capsule().stroke(RED_HIGHLIGHT, 2.0).padding(Auto),
+            arc(0.0, 1.5 * std::f32::consts::PI)
+                .stroke(AZURE_HIGHLIGHT, 4.0)
+                .padding(Auto),
+            line([0.0, 0.0], [1.0, 1.0]).padding(Auto),
+            polygon([[0.0, 0.0], [1.0, 0.0], [0.5, 1.0]])
+                .fill(CONTROL_BACKGROUND)
+                .stroke(WHITE, 1.0)
+                .padding(Auto),
+            // An attack, decay, sustain and release envelope.
+            path()
+                .move_to([0.0, 0.0])
+                .quad_to([0.1, 1.0], [0.25, 1.0])
+                .cubic_to([0.3, 0.6], [0.4, 0.6], [0.5, 0.6])
+                .line_to([0.75, 0.6])
+                .quad_to([0.8, 0.0], [1.0, 0.0])
+                .stroke(AZURE_HIGHLIGHT, 2.0)
+                .padding(Auto),
+        )),
     ))
     .run()
 }
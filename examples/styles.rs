@@ -0,0 +1,89 @@
+use rui::*;
+
+/// An outlined button which fills in while pressed.
+struct OutlineButtonStyle;
+
+impl ButtonStyle for OutlineButtonStyle {
+    fn body(&self, config: ButtonConfiguration, theme: &Theme) -> AnyView {
+        let fill = if config.state.pressed {
+            theme.accent_background
+        } else {
+            theme.background
+        };
+        let border = if config.state.hovered || config.state.focused {
+            theme.accent
+        } else {
+            theme.secondary_text
+        };
+        any_view(config.label.padding(Auto).background(zstack((
+            rectangle().corner_radius(8.0).color(border),
+            rectangle().corner_radius(7.0).color(fill).padding(1.0),
+        ))))
+    }
+}
+
+/// A square check box instead of a switch.
+struct CheckboxStyle;
+
+impl ToggleStyle for CheckboxStyle {
+    fn body(&self, config: ToggleConfiguration, theme: &Theme) -> AnyView {
+        let color = if config.on {
+            theme.accent
+        } else {
+            theme.control_background
+        };
+        any_view(
+            rectangle()
+                .corner_radius(3.0)
+                .color(color)
+                .size([20.0, 20.0]),
+        )
+    }
+}
+
+/// A flat bar showing the value.
+struct BarSliderStyle;
+
+impl SliderStyle for BarSliderStyle {
+    fn body(&self, config: SliderConfiguration, theme: &Theme) -> AnyView {
+        let theme = *theme;
+        any_view(canvas(move |_, rect, vger| {
+            let paint = vger.color_paint(theme.track);
+            vger.fill_rect(rect, 0.0, paint);
+            let mut filled = rect;
+            filled.size.width *= config.value;
+            let paint = vger.color_paint(if config.state.pressed {
+                theme.accent
+            } else {
+                theme.accent_background
+            });
+            vger.fill_rect(filled, 0.0, paint);
+        }))
+    }
+}
+
+#[derive(Default)]
+struct Settings {
+    on: bool,
+    level: f32,
+}
+
+make_lens!(OnLens, Settings, bool, on);
+make_lens!(LevelLens, Settings, f32, level);
+
+fn main() {
+    state(Settings::default, |s, cx| {
+        vstack((
+            button("Outlined", |_| println!("clicked")).padding(Auto),
+            toggle(bind(s, OnLens {})).padding(Auto),
+            hslider(bind(s, LevelLens {}))
+                .size([200.0, 20.0])
+                .padding(Auto),
+            format!("{:.2}", cx[s].level).padding(Auto),
+        ))
+        .button_style(OutlineButtonStyle)
+        .toggle_style(CheckboxStyle)
+        .slider_style(BarSliderStyle)
+    })
+    .run()
+}
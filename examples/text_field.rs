@@ -0,0 +1,39 @@
+use rui::*;
+
+#[derive(Default)]
+struct Login {
+    name: String,
+    password: String,
+    port: String,
+}
+
+make_lens!(NameLens, Login, String, name);
+make_lens!(PasswordLens, Login, String, password);
+make_lens!(PortLens, Login, String, port);
+
+fn main() {
+    state(Login::default, |login, cx| {
+        let port_error = cx[login].port.is_empty();
+        vstack((
+            text_field(bind(login, NameLens {}))
+                .placeholder("Name")
+                .max_length(32)
+                .size([300.0, 30.0])
+                .padding(Auto),
+            text_field(bind(login, PasswordLens {}))
+                .placeholder("Password")
+                .password(true)
+                .size([300.0, 30.0])
+                .padding(Auto),
+            text_field(bind(login, PortLens {}))
+                .placeholder("Port")
+                .input_filter(InputFilter::Digits)
+                .max_length(5)
+                .error(port_error)
+                .on_submit(move |cx| println!("connecting to port {}", cx[login].port))
+                .size([300.0, 30.0])
+                .padding(Auto),
+        ))
+    })
+    .run()
+}
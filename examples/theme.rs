@@ -0,0 +1,29 @@
+use rui::*;
+
+#[derive(Default)]
+struct Settings {
+    light: bool,
+    level: f32,
+}
+
+make_lens!(LightLens, Settings, bool, light);
+make_lens!(LevelLens, Settings, f32, level);
+
+fn main() {
+    state(Settings::default, |s, cx| {
+        let theme = if cx[s].light {
+            Theme::light()
+        } else {
+            Theme::dark()
+        };
+        vstack((
+            hstack((text("Light theme"), toggle(bind(s, LightLens {})))).padding(Auto),
+            button("Button", |_| println!("clicked")).padding(Auto),
+            hslider(bind(s, LevelLens {})).padding(Auto),
+            knob(bind(s, LevelLens {})).size([50.0, 50.0]).padding(Auto),
+        ))
+        .background(rectangle().color(theme.background))
+        .env(theme)
+    })
+    .run()
+}
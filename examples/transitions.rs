@@ -0,0 +1,52 @@
+use rui::*;
+
+fn main() {
+    state(
+        || (true, vec![1, 2, 3], 4),
+        |s, cx| {
+            let (shown, items, _) = &cx[s];
+            vstack((
+                hstack((
+                    button("Toggle", move |cx| cx[s].0 = !cx[s].0),
+                    button("Add", move |cx| {
+                        let next = cx[s].2;
+                        cx[s].1.push(next);
+                        cx[s].2 += 1;
+                    }),
+                    button("Remove", move |cx| {
+                        if !cx[s].1.is_empty() {
+                            cx[s].1.remove(0);
+                        }
+                    }),
+                ))
+                .padding(Auto),
+                cond(
+                    *shown,
+                    rectangle()
+                        .corner_radius(10.0)
+                        .color(AZURE_HIGHLIGHT)
+                        .size([120.0, 60.0])
+                        .transition(Transition::opacity().combined(Transition::scale()))
+                        .padding(Auto),
+                    EmptyView {},
+                ),
+                hlist(items.clone(), |item| {
+                    format!("{}", item)
+                        .padding(Auto)
+                        .background(
+                            rectangle()
+                                .corner_radius(5.0)
+                                .color(BUTTON_BACKGROUND_COLOR),
+                        )
+                        .transition(
+                            Transition::slide(Edge::Bottom)
+                                .combined(Transition::opacity())
+                                .animation(Animation::spring(170.0, 20.0)),
+                        )
+                        .padding(Auto)
+                }),
+            ))
+        },
+    )
+    .run()
+}
@@ -0,0 +1,427 @@
+use crate::*;
+
+/// How a value moves to a new target.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Animation {
+    /// A damped spring, which carries velocity into a new target.
+    Spring { stiffness: f32, damping: f32 },
+
+    /// Accelerates, then slows down to stop at the target after `duration` seconds.
+    EaseInOut { duration: f32 },
+}
+
+impl Animation {
+    /// A spring pulling with `stiffness` per unit of distance, slowed by
+    /// `damping` per unit of velocity. `spring(170.0, 26.0)` settles
+    /// quickly without bouncing.
+    pub fn spring(stiffness: f32, damping: f32) -> Self {
+        Self::Spring { stiffness, damping }
+    }
+
+    pub fn ease_in_out(duration: f32) -> Self {
+        Self::EaseInOut { duration }
+    }
+}
+
+/// Values which can be animated.
+pub trait Animatable: Copy + PartialEq + 'static {
+    fn add(self, rhs: Self) -> Self;
+    fn sub(self, rhs: Self) -> Self;
+    fn scale(self, s: f32) -> Self;
+
+    /// Size of the value, used to tell when an animation has settled.
+    fn magnitude(self) -> f32;
+}
+
+impl Animatable for f32 {
+    fn add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+
+    fn scale(self, s: f32) -> Self {
+        self * s
+    }
+
+    fn magnitude(self) -> f32 {
+        self.abs()
+    }
+}
+
+impl Animatable for LocalOffset {
+    fn add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+
+    fn scale(self, s: f32) -> Self {
+        self * s
+    }
+
+    fn magnitude(self) -> f32 {
+        self.length()
+    }
+}
+
+impl Animatable for LocalSize {
+    fn add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+
+    fn scale(self, s: f32) -> Self {
+        self * s
+    }
+
+    fn magnitude(self) -> f32 {
+        self.to_vector().length()
+    }
+}
+
+impl Animatable for Color {
+    fn add(self, rhs: Self) -> Self {
+        Color::new(
+            self.r + rhs.r,
+            self.g + rhs.g,
+            self.b + rhs.b,
+            self.a + rhs.a,
+        )
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        Color::new(
+            self.r - rhs.r,
+            self.g - rhs.g,
+            self.b - rhs.b,
+            self.a - rhs.a,
+        )
+    }
+
+    fn scale(self, s: f32) -> Self {
+        Color::new(self.r * s, self.g * s, self.b * s, self.a * s)
+    }
+
+    fn magnitude(self) -> f32 {
+        self.r
+            .abs()
+            .max(self.g.abs())
+            .max(self.b.abs())
+            .max(self.a.abs())
+    }
+}
+
+/// Distance and speed below which an animation snaps to its target.
+const SETTLE_EPSILON: f32 = 0.001;
+
+/// Longest step when integrating a spring, in seconds.
+const SPRING_STEP: f32 = 1.0 / 240.0;
+
+#[derive(Clone, Copy, Debug)]
+struct Run<T> {
+    animation: Animation,
+    from: T,
+    initial_velocity: T,
+    elapsed: f32,
+}
+
+/// A value which moves towards its target over time.
+///
+/// Keep one in state and call `step` from `anim` to animate by hand.
+/// Built-in modifiers use one for each value they animate.
+#[derive(Clone, Copy, Debug)]
+pub struct Animated<T> {
+    value: T,
+    velocity: T,
+    target: T,
+    run: Option<Run<T>>,
+}
+
+impl<T: Animatable> Animated<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            velocity: value.scale(0.0),
+            target: value,
+            run: None,
+        }
+    }
+
+    /// The current value.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// How fast the value is changing, per second.
+    pub fn velocity(&self) -> T {
+        self.velocity
+    }
+
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Starts moving to `target`, or jumps there if `animation` is `None`.
+    /// A running animation continues from its current value and velocity.
+    pub fn set(&mut self, target: T, animation: Option<Animation>) {
+        if target == self.target {
+            return;
+        }
+        self.target = target;
+        match animation {
+            Some(animation) => {
+                self.run = Some(Run {
+                    animation,
+                    from: self.value,
+                    initial_velocity: self.velocity,
+                    elapsed: 0.0,
+                })
+            }
+            None => self.finish(),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.value = self.target;
+        self.velocity = self.target.scale(0.0);
+        self.run = None;
+    }
+
+    /// Advances the animation by `dt` seconds. Returns true while it's running.
+    pub fn step(&mut self, dt: f32) -> bool {
+        let Some(run) = &mut self.run else {
+            return false;
+        };
+        run.elapsed += dt;
+        let run = *run;
+
+        match run.animation {
+            Animation::Spring { stiffness, damping } => {
+                let steps = (dt / SPRING_STEP).ceil().max(1.0);
+                let h = dt / steps;
+                for _ in 0..steps as usize {
+                    let accel = self
+                        .target
+                        .sub(self.value)
+                        .scale(stiffness)
+                        .sub(self.velocity.scale(damping));
+                    self.velocity = self.velocity.add(accel.scale(h));
+                    self.value = self.value.add(self.velocity.scale(h));
+                }
+                if self.target.sub(self.value).magnitude() < SETTLE_EPSILON
+                    && self.velocity.magnitude() < SETTLE_EPSILON
+                {
+                    self.finish();
+                }
+            }
+            Animation::EaseInOut { duration } => {
+                if run.elapsed >= duration {
+                    self.finish();
+                } else {
+                    // Cubic Hermite curve from the starting value and velocity
+                    // to the target at rest. Without a starting velocity it is
+                    // the smoothstep ease-in-out.
+                    let u = run.elapsed / duration;
+                    let (u2, u3) = (u * u, u * u * u);
+                    let start = run.from.scale(2.0 * u3 - 3.0 * u2 + 1.0);
+                    let tangent = run.initial_velocity.scale((u3 - 2.0 * u2 + u) * duration);
+                    let end = self.target.scale(3.0 * u2 - 2.0 * u3);
+                    self.value = start.add(tangent).add(end);
+
+                    let d_start = run.from.scale((6.0 * u2 - 6.0 * u) / duration);
+                    let d_tangent = run.initial_velocity.scale(3.0 * u2 - 4.0 * u + 1.0);
+                    let d_end = self.target.scale((6.0 * u - 6.0 * u2) / duration);
+                    self.velocity = d_start.add(d_tangent).add(d_end);
+                }
+            }
+        }
+        self.run.is_some()
+    }
+}
+
+/// Calls `f`, animating the changes it makes to `offset`, `size`, `padding`
+/// and shape colors with `animation`.
+///
+/// ```no_run
+/// # use rui::*;
+/// state(|| false, |wide, cx| {
+///     rectangle()
+///         .size(if cx[wide] { [200.0, 50.0] } else { [50.0, 50.0] })
+///         .tap(move |cx| {
+///             with_animation(cx, Animation::spring(170.0, 26.0), |cx| cx[wide] = !cx[wide])
+///         })
+/// });
+/// ```
+pub fn with_animation<R>(
+    cx: &mut Context,
+    animation: Animation,
+    f: impl FnOnce(&mut Context) -> R,
+) -> R {
+    let outer = cx.begin_transaction(animation);
+    let result = f(cx);
+    cx.end_transaction(outer);
+    result
+}
+
+impl Context {
+    /// The value to show for `target` in the view with `id`. Changes of
+    /// the target made in `with_animation` animate, others show at once.
+    pub(crate) fn animated<T: Animatable>(&mut self, id: ViewId, target: T) -> T {
+        // Until views have been retargeted to the changes of a
+        // `with_animation`, keep showing what they were showing.
+        let retarget = self.transaction.is_some() || self.pending_transaction.is_none();
+        let animation = self.transaction;
+        let animated = self.view_state(id, || Animated::new(target));
+        if retarget {
+            animated.set(target, animation);
+        }
+        animated.value()
+    }
+
+    /// Like `animated`, but also advances the animation by `dt` seconds.
+    /// Call it on `Event::Anim`.
+    pub(crate) fn step_animated<T: Animatable>(&mut self, id: ViewId, target: T, dt: f32) -> T {
+        self.animated(id, target);
+        let holder = self.state_map.get_mut(&id).unwrap();
+        let animated = holder.state.downcast_mut::<Animated<T>>().unwrap();
+        if animated.is_running() {
+            let running = animated.step(dt);
+            let value = animated.value();
+            holder.dirty = true;
+            self.set_dirty();
+            if running {
+                self.request_frame();
+            }
+            value
+        } else {
+            animated.value()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_animated() {
+        let mut x = Animated::new(0.0);
+        x.set(1.0, Some(Animation::ease_in_out(1.0)));
+        assert!(x.step(0.5));
+        assert!((x.value() - 0.5).abs() < 1e-6);
+
+        // Retargeting keeps the velocity, so it keeps going up for a bit.
+        x.set(0.0, Some(Animation::ease_in_out(1.0)));
+        assert!(x.step(0.1));
+        assert!(x.value() > 0.5);
+        assert!(!x.step(1.0));
+        assert_eq!(x.value(), 0.0);
+
+        let mut y = Animated::new(0.0);
+        y.set(10.0, Some(Animation::spring(170.0, 26.0)));
+        let mut frames = 0;
+        while y.step(1.0 / 60.0) {
+            frames += 1;
+        }
+        assert!(frames > 10 && frames < 120);
+        assert_eq!(y.value(), 10.0);
+
+        y.set(20.0, None);
+        assert_eq!(y.value(), 20.0);
+        assert!(!y.is_running());
+    }
+
+    #[test]
+    fn test_with_animation() {
+        let mut cx = Context::new();
+        let clock = VirtualClock::new();
+        cx.set_clock(clock.clone());
+
+        let ui = state(
+            || 0.0,
+            |x, cx| {
+                rectangle().offset([cx[x], 0.0]).tap(move |cx| {
+                    with_animation(cx, Animation::ease_in_out(0.5), |cx| cx[x] = 100.0)
+                })
+            },
+        );
+
+        let mut path = vec![0];
+        let mut layout = |cx: &mut Context| {
+            ui.layout(
+                &mut path,
+                &mut LayoutArgs {
+                    sz: [100.0, 100.0].into(),
+                    cx,
+                    text_bounds: &mut |_, _, _| LocalRect::zero(),
+                },
+            );
+        };
+        layout(&mut cx);
+        cx.animate(&ui);
+
+        let offset_id = cx.view_id(&vec![0, 0, 0]);
+        let offset = |cx: &Context| {
+            cx.state_map[&offset_id]
+                .state
+                .downcast_ref::<Animated<LocalOffset>>()
+                .unwrap()
+                .value()
+                .x
+        };
+
+        for event in [
+            Event::TouchBegin {
+                id: 0,
+                position: [50.0, 50.0].into(),
+            },
+            Event::TouchEnd {
+                id: 0,
+                position: [50.0, 50.0].into(),
+            },
+        ] {
+            cx.process(&ui, &event);
+        }
+        assert!(cx.transaction.is_none());
+
+        let mut xs = vec![];
+        for _ in 0..40 {
+            clock.advance(1.0 / 60.0);
+            cx.animate(&ui);
+            xs.push(offset(&cx));
+        }
+        assert!(xs[0] > 0.0 && xs[0] < 10.0);
+        assert!(xs.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(xs[39], 100.0);
+        assert!(!cx.wants_frame());
+
+        // Changes outside of with_animation show at once.
+        let x = StateHandle::<f32>::new(cx.view_id(&vec![0]));
+        cx[x] = 50.0;
+        cx.animate(&ui);
+        assert_eq!(offset(&cx), 50.0);
+
+        // So do changes after it, even mid-animation.
+        with_animation(&mut cx, Animation::ease_in_out(0.5), |cx| cx[x] = 0.0);
+        clock.advance(1.0 / 60.0);
+        cx.animate(&ui);
+        assert!(offset(&cx) > 40.0 && offset(&cx) < 50.0);
+        cx[x] = 20.0;
+        clock.advance(1.0 / 60.0);
+        cx.animate(&ui);
+        assert_eq!(offset(&cx), 20.0);
+    }
+}
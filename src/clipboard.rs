@@ -0,0 +1,28 @@
+/// Access to the clipboard, used by text editors for copy, cut and paste.
+///
+/// rui doesn't depend on a platform clipboard crate. Install one with
+/// `Context::set_clipboard` to share text with other applications.
+pub trait Clipboard {
+    /// Returns the text on the clipboard, if any.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Replaces the contents of the clipboard.
+    fn set_text(&mut self, text: String);
+}
+
+/// A clipboard which only lives within the application. This is
+/// the default, and is useful for tests.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
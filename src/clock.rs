@@ -0,0 +1,107 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Source of the time passed to animations with `Event::Anim`.
+///
+/// The default measures real time. Install a `VirtualClock` with
+/// `Context::set_clock` to step animations by hand, for example in tests.
+pub trait Clock {
+    /// Seconds since some fixed point, such as the start of the application.
+    /// Called once per frame.
+    fn now(&mut self) -> f64;
+}
+
+/// Measures real time.
+#[derive(Clone, Debug)]
+pub struct SystemClock {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: std::time::Instant,
+
+    /// std::time::Instant isn't available on the web, so count frames instead.
+    #[cfg(target_arch = "wasm32")]
+    frames: u64,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: std::time::Instant::now(),
+            #[cfg(target_arch = "wasm32")]
+            frames: 0,
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn now(&mut self) -> f64 {
+        self.start.elapsed().as_secs_f64()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn now(&mut self) -> f64 {
+        self.frames += 1;
+        self.frames as f64 / 60.0
+    }
+}
+
+/// A clock which only moves when told to. Clones share the same time, so keep
+/// one to advance the clock after passing another to `Context::set_clock`.
+#[derive(Clone, Debug, Default)]
+pub struct VirtualClock {
+    time: Rc<Cell<f64>>,
+}
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the clock forward by `seconds`.
+    pub fn advance(&self, seconds: f64) {
+        self.time.set(self.time.get() + seconds);
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time.get()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&mut self) -> f64 {
+        self.time.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn test_virtual_clock() {
+        let mut cx = Context::new();
+        let clock = VirtualClock::new();
+        cx.set_clock(clock.clone());
+
+        // Records each dt, and keeps animating for three frames.
+        let ui = state(Vec::<f32>::new, |s, _| {
+            rectangle().anim(move |cx, dt| {
+                cx[s].push(dt);
+                if cx[s].len() < 3 {
+                    cx.request_frame();
+                }
+            })
+        });
+
+        for _ in 0..4 {
+            clock.advance(0.5);
+            cx.animate(&ui);
+        }
+        let s = StateHandle::<Vec<f32>>::new(cx.view_id(&vec![0]));
+        // The first frame, and the one after the animation stopped, use 1/60 s.
+        assert_eq!(cx[s], vec![1.0 / 60.0, 0.5, 0.5, 1.0 / 60.0]);
+        assert!(!cx.wants_frame());
+    }
+}
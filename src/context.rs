@@ -15,13 +15,43 @@ pub type LocalSize = Size2D<f32, LocalSpace>;
 pub type LocalPoint = Point2D<f32, LocalSpace>;
 pub type WorldRect = Rect<f32, WorldSpace>;
 pub type WorldPoint = Point2D<f32, WorldSpace>;
+pub type WorldOffset = Vector2D<f32, WorldSpace>;
 pub type LocalToWorld = Transform2D<f32, LocalSpace, WorldSpace>;
 pub type WorldToLocal = Transform2D<f32, WorldSpace, LocalSpace>;
+pub type LocalTransform = Transform2D<f32, LocalSpace, LocalSpace>;
 
 #[derive(Clone, Eq, PartialEq)]
 pub struct CommandInfo {
     pub path: Arc<str>,
     pub key: Option<HotKey>,
+
+    /// Can the command currently run?
+    pub enabled: bool,
+
+    /// Should the command be shown with a check mark?
+    pub checked: bool,
+}
+
+/// A view which can take the keyboard focus.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusInfo {
+    pub id: ViewId,
+
+    /// Views are visited by `Tab` in ascending order, and in
+    /// tree order among views of the same order.
+    pub order: i32,
+
+    /// Bounds of the view, in world space.
+    pub rect: WorldRect,
+
+    /// The innermost focus group containing the view.
+    pub group: Option<ViewId>,
+
+    /// `Tab` goes to the view instead of moving the focus.
+    pub accepts_tab: bool,
+
+    /// The view has a `text_input` modifier, so it edits text.
+    pub text_input: bool,
 }
 
 pub const DEBUG_LAYOUT: bool = false;
@@ -69,6 +99,9 @@ pub struct Context {
     /// Previous touch/mouse positions.
     pub(crate) previous_position: [LocalPoint; 16],
 
+    /// Undo groups opened by drags, closed when the touch ends.
+    pub(crate) drag_undo: [Option<UndoManager>; 16],
+
     /// Current mouse button for event handling.
     pub(crate) mouse_button: Option<MouseButton>,
 
@@ -81,6 +114,10 @@ pub struct Context {
     /// The view that has the keyboard focus.
     pub(crate) focused_id: Option<ViewId>,
 
+    /// Should the focus ring be drawn? Only true when
+    /// the focus was moved by keyboard or programmatically.
+    pub(crate) focus_visible: bool,
+
     /// The current title of the window
     pub window_title: Arc<str>,
 
@@ -122,6 +159,77 @@ pub struct Context {
 
     /// Value of grab_cursor before processing event.
     pub(crate) prev_grab_cursor: bool,
+
+    /// Number of clicks in quick succession at the same spot,
+    /// for the latest mouse press. 2 for a double-click.
+    pub(crate) click_count: u32,
+
+    /// Are writes through undoable bindings recorded? False while undoing.
+    pub(crate) record_undo: bool,
+
+    /// Checks for writes made with `get_mut` on undoable bindings,
+    /// which can only be recorded once the write is done.
+    pub(crate) pending_undo: Vec<PendingUndo>,
+
+    /// Clipboard used for copy and paste.
+    clipboard: Box<dyn Clipboard>,
+
+    /// Caret of the focused text editor, used to position the IME
+    /// candidate window. Set while drawing.
+    pub(crate) ime_rect: Option<WorldRect>,
+
+    /// Value of ime_rect when the window's IME was last updated.
+    #[cfg(feature = "winit")]
+    pub(crate) prev_ime_rect: Option<WorldRect>,
+
+    /// Commands gathered from the whole view tree.
+    pub(crate) registered_commands: Vec<CommandInfo>,
+
+    /// Has the view tree changed since the commands were gathered?
+    pub(crate) commands_stale: bool,
+
+    /// Commands sent by views, to be dispatched from the root.
+    pending_commands: Vec<Event>,
+
+    /// Time source for animations.
+    clock: Box<dyn Clock>,
+
+    /// Clock time of the previous animation frame.
+    last_frame: Option<f64>,
+
+    /// Has a view asked for another frame during this one?
+    frame_requested: bool,
+
+    /// Did the previous frame ask for another?
+    animating: bool,
+
+    /// Number of frames so far.
+    pub(crate) frame: u64,
+
+    /// Animation of the `with_animation` being run, or of the views
+    /// being retargeted to the changes made in it.
+    pub(crate) transaction: Option<Animation>,
+
+    /// Animation of a `with_animation` whose changes views haven't been
+    /// retargeted to yet.
+    pub(crate) pending_transaction: Option<Animation>,
+
+    /// Animation of the latest `with_animation`, and the frame in which it ended.
+    last_transaction: Option<(Animation, u64)>,
+
+    /// Opacity of the views being drawn.
+    pub(crate) opacity: f32,
+
+    /// Was a `text_input` modifier found while listing the focusables
+    /// inside a focus view?
+    pub(crate) found_text_input: bool,
+
+    /// Views with a `transition`, including removed ones which are leaving.
+    pub(crate) transitions: HashMap<ViewId, TransitionState>,
+
+    /// Bounds of the views with `matched_geometry`, by key, and which
+    /// view they belong to.
+    pub(crate) matched_geometry: HashMap<u64, (ViewId, WorldRect)>,
 }
 
 impl Default for Context {
@@ -139,10 +247,12 @@ impl Context {
             touches: [ViewId::default(); 16],
             starts: [LocalPoint::zero(); 16],
             previous_position: [LocalPoint::zero(); 16],
+            drag_undo: Default::default(),
             mouse_button: None,
             mouse_buttons: Default::default(),
             key_mods: Default::default(),
             focused_id: None,
+            focus_visible: false,
             window_title: "rui".into(),
             fullscreen: false,
             state_map: HashMap::new(),
@@ -157,6 +267,28 @@ impl Context {
             render_dirty: false,
             grab_cursor: false,
             prev_grab_cursor: false,
+            click_count: 1,
+            record_undo: true,
+            pending_undo: vec![],
+            clipboard: Box::new(MemoryClipboard::default()),
+            ime_rect: None,
+            #[cfg(feature = "winit")]
+            prev_ime_rect: None,
+            registered_commands: vec![],
+            commands_stale: true,
+            pending_commands: vec![],
+            clock: Box::new(SystemClock::default()),
+            last_frame: None,
+            frame_requested: false,
+            animating: false,
+            frame: 0,
+            transaction: None,
+            pending_transaction: None,
+            last_transaction: None,
+            opacity: 1.0,
+            found_text_input: false,
+            transitions: HashMap::new(),
+            matched_geometry: HashMap::new(),
         }
     }
 
@@ -174,24 +306,14 @@ impl Context {
             self.window_size = window_size;
         }
 
-        let mut path = vec![0];
+        self.animate(view);
 
-        // Run any animations.
-        let mut actions = vec![];
-        view.process(&Event::Anim, &mut path, self, &mut actions);
-        assert!(path.len() == 1);
+        let mut path = vec![0];
 
         if self.dirty {
-            // Clean up state and layout.
-            let mut keep = vec![];
-            view.gc(&mut path, self, &mut keep);
-            assert!(path.len() == 1);
-            let keep_set = HashSet::<ViewId>::from_iter(keep);
-            self.state_map.retain(|k, _| keep_set.contains(k));
+            self.refresh_commands(view);
 
-            let mut new_layout = self.layout.clone();
-            new_layout.retain(|k, _| keep_set.contains(&self.view_id(k)));
-            self.layout = new_layout;
+            self.collect_garbage(view);
 
             // Get a new accesskit tree.
             let mut nodes = vec![];
@@ -236,6 +358,118 @@ impl Context {
         }
     }
 
+    /// Cleans up the state and layout of views which are no longer in the tree.
+    pub(crate) fn collect_garbage(&mut self, view: &impl View) {
+        self.prune_matched_geometry();
+
+        let mut path = vec![0];
+        let mut keep = vec![];
+        view.gc(&mut path, self, &mut keep);
+        assert!(path.len() == 1);
+        self.keep_exiting_transitions(&mut keep);
+        let keep_set = HashSet::<ViewId>::from_iter(keep);
+        self.state_map.retain(|k, _| keep_set.contains(k));
+
+        let mut new_layout = self.layout.clone();
+        new_layout.retain(|k, _| keep_set.contains(&self.view_id(k)));
+        self.layout = new_layout;
+    }
+
+    /// Sends `Event::Anim` with the time from the clock. Called by `update`
+    /// for each frame. Call it directly to step animations in tests.
+    pub fn animate(&mut self, view: &impl View) {
+        self.retarget(view);
+
+        // Views have been drawn with the changes of a transaction by the
+        // end of the frame after it ended.
+        self.frame += 1;
+        if let Some((_, frame)) = self.last_transaction {
+            if self.frame > frame + 1 {
+                self.last_transaction = None;
+            }
+        }
+
+        let time = self.clock.now();
+        // After being idle, don't jump ahead by the time spent idle.
+        let dt = match self.last_frame {
+            Some(last) if self.animating => (time - last) as f32,
+            _ => 1.0 / 60.0,
+        };
+        self.last_frame = Some(time);
+        self.frame_requested = false;
+
+        let mut path = vec![0];
+        let mut actions = vec![];
+        view.process(&Event::Anim { dt, time }, &mut path, self, &mut actions);
+        assert!(path.len() == 1);
+        self.step_transitions(dt);
+
+        self.animating = self.frame_requested;
+    }
+
+    /// Asks for another frame after this one, even if nothing else changes.
+    /// Call it from `anim` until the animation settles, after which the
+    /// event loop goes idle.
+    pub fn request_frame(&mut self) {
+        self.frame_requested = true;
+    }
+
+    /// Starts animating changes with `animation`. Returns the animation of
+    /// the enclosing transaction, to pass to `end_transaction`.
+    pub(crate) fn begin_transaction(&mut self, animation: Animation) -> Option<Animation> {
+        self.transaction.replace(animation)
+    }
+
+    /// Stops animating changes. Views are retargeted to the changes made
+    /// since `begin_transaction` once the current event has been processed.
+    pub(crate) fn end_transaction(&mut self, outer: Option<Animation>) {
+        if let Some(animation) = std::mem::replace(&mut self.transaction, outer) {
+            self.pending_transaction = Some(animation);
+            self.last_transaction = Some((animation, self.frame));
+        }
+    }
+
+    /// Sends a zero-length `Event::Anim` inside the transaction of the latest
+    /// `with_animation`, so views start animating to the targets its changes
+    /// gave them. Changes made afterwards show at once.
+    fn retarget(&mut self, view: &impl View) {
+        if self.transaction.is_some() {
+            return;
+        }
+        let Some(animation) = self.pending_transaction.take() else {
+            return;
+        };
+        self.transaction = Some(animation);
+        let mut path = vec![0];
+        let mut actions = vec![];
+        let time = self.clock.now();
+        view.process(
+            &Event::Anim { dt: 0.0, time },
+            &mut path,
+            self,
+            &mut actions,
+        );
+        assert!(path.len() == 1);
+        self.transaction = None;
+    }
+
+    /// Animation of the latest `with_animation`, for views which only see
+    /// its changes when they're drawn.
+    pub(crate) fn last_transaction(&self) -> Option<Animation> {
+        self.last_transaction.map(|(animation, _)| animation)
+    }
+
+    /// Did a view call `request_frame` during the last frame, or since?
+    pub fn wants_frame(&self) -> bool {
+        self.animating || self.frame_requested
+    }
+
+    /// Replaces the clock for animations, for example with a `VirtualClock`.
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Box::new(clock);
+        self.last_frame = None;
+    }
+
     /// Redraw the UI using wgpu.
     pub fn render(
         &mut self,
@@ -278,8 +512,12 @@ impl Context {
         // Center the root view in the window.
         self.root_offset = ((local_window_size - sz) / 2.0).into();
 
+        vger.save();
         vger.translate(self.root_offset);
+        self.ime_rect = None;
         view.draw(&mut path, &mut DrawArgs { cx: self, vger });
+        vger.restore();
+        self.draw_exiting_transitions(vger);
         self.enable_dirty = true;
 
         if self.render_dirty {
@@ -298,6 +536,7 @@ impl Context {
 
         self.dirty_region.clear();
 
+        let background = self.theme().background;
         let texture_view = frame
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
@@ -307,7 +546,12 @@ impl Context {
                 view: &texture_view,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: background.r as f64,
+                        g: background.g as f64,
+                        b: background.b as f64,
+                        a: background.a as f64,
+                    }),
                     store: wgpu::StoreOp::Store,
                 },
             })],
@@ -320,7 +564,40 @@ impl Context {
     }
 
     /// Process a UI event.
+    ///
+    /// Key presses matching a command's hotkey are dispatched as
+    /// `Event::Command` instead.
     pub fn process(&mut self, view: &impl View, event: &Event) {
+        if let Event::Key(key) = event {
+            if self.commands_stale {
+                self.refresh_commands(view);
+            }
+            if let Some(name) = self.hotkey_command(key) {
+                self.process(view, &Event::Command(name, None));
+                return;
+            }
+        }
+
+        if let Event::Key(Key::Tab) = event {
+            if !self.focused_accepts_tab(view) {
+                self.focus_next(view, self.key_mods.shift);
+                return;
+            }
+        }
+
+        let dir = match event {
+            Event::Key(Key::ArrowLeft) => Some([-1.0, 0.0]),
+            Event::Key(Key::ArrowRight) => Some([1.0, 0.0]),
+            Event::Key(Key::ArrowUp) => Some([0.0, 1.0]),
+            Event::Key(Key::ArrowDown) => Some([0.0, -1.0]),
+            _ => None,
+        };
+        if let Some(dir) = dir {
+            if self.focus_direction(view, dir.into()) {
+                return;
+            }
+        }
+
         let mut actions = vec![];
         let mut path = vec![0];
         view.process(
@@ -335,6 +612,33 @@ impl Context {
                 log::debug!("unhandled action: {:?}", action.type_id());
             }
         }
+
+        for event in std::mem::take(&mut self.pending_commands) {
+            self.process(view, &event);
+        }
+
+        // Close the undo group of a drag whose view went away mid-gesture.
+        if let Event::TouchEnd { id, .. } = event {
+            if let Some(undo) = self.drag_undo[*id].take() {
+                self.flush_undo();
+                undo.end_group();
+            }
+        }
+        self.flush_undo();
+        self.retarget(view);
+
+        // Views such as the command palette list the commands, so
+        // pick up any the event added or removed.
+        if self.commands_stale {
+            self.refresh_commands(view);
+        }
+    }
+
+    /// Dispatches a command to the whole view tree once the
+    /// current event has been processed.
+    pub fn send_command(&mut self, name: &str, arg: Option<CommandArg>) {
+        self.pending_commands.push(Event::Command(name.into(), arg));
+        self.set_dirty();
     }
 
     /// Get menu commands.
@@ -343,6 +647,169 @@ impl Context {
         view.commands(&mut path, self, cmds);
     }
 
+    /// Number of clicks in quick succession for the latest mouse press:
+    /// 1 for a single click, 2 for a double-click and so on.
+    pub fn click_count(&self) -> u32 {
+        self.click_count
+    }
+
+    /// Replaces the clipboard, for example with one backed by the system clipboard.
+    pub fn set_clipboard(&mut self, clipboard: impl Clipboard + 'static) {
+        self.clipboard = Box::new(clipboard);
+    }
+
+    /// The clipboard used for copy and paste.
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        self.clipboard.as_mut()
+    }
+
+    /// The undo history from the environment.
+    pub fn undo_manager(&mut self) -> UndoManager {
+        self.init_env(&UndoManager::default)
+    }
+
+    /// Records the writes made with `get_mut` on undoable bindings.
+    pub(crate) fn flush_undo(&mut self) {
+        for check in std::mem::take(&mut self.pending_undo) {
+            check(self);
+        }
+    }
+
+    /// Accepts text input from the IME, placing its candidate window near `rect`,
+    /// which is usually the caret. Call this while drawing the focused editor.
+    pub fn set_ime_cursor_area(&mut self, rect: WorldRect) {
+        self.ime_rect = Some(rect);
+    }
+
+    /// Gives the keyboard focus to a view.
+    pub fn focus(&mut self, id: ViewId) {
+        if self.focused_id != Some(id) {
+            // The newly focused view sets it again when drawn.
+            self.ime_rect = None;
+        }
+        self.focused_id = Some(id);
+        self.focus_visible = true;
+        self.set_dirty();
+    }
+
+    /// Removes the keyboard focus.
+    pub fn blur(&mut self) {
+        self.focused_id = None;
+        self.ime_rect = None;
+        self.set_dirty();
+    }
+
+    /// The view which has the keyboard focus, if any.
+    pub fn focused(&self) -> Option<ViewId> {
+        self.focused_id
+    }
+
+    fn focusables(&mut self, view: &impl View) -> Vec<FocusInfo> {
+        let mut ids = vec![];
+        let mut path = vec![0];
+        view.focusables(&mut path, LocalToWorld::identity(), self, &mut ids);
+        ids
+    }
+
+    /// Moves the focus to the next (or previous) focusable view, wrapping around.
+    pub(crate) fn focus_next(&mut self, view: &impl View, backward: bool) {
+        let mut ids = self.focusables(view);
+
+        // Stable, so tree order is kept for equal orders.
+        ids.sort_by_key(|f| f.order);
+
+        let n = ids.len();
+        if n == 0 {
+            return;
+        }
+
+        let current = self
+            .focused_id
+            .and_then(|id| ids.iter().position(|f| f.id == id));
+
+        let next = match (current, backward) {
+            (None, false) => 0,
+            (None, true) => n - 1,
+            (Some(i), false) => (i + 1) % n,
+            (Some(i), true) => (i + n - 1) % n,
+        };
+
+        self.focus(ids[next].id);
+    }
+
+    /// Does the focused view take `Tab` for itself?
+    fn focused_accepts_tab(&mut self, view: &impl View) -> bool {
+        match self.focused_id {
+            Some(id) => self
+                .focusables(view)
+                .iter()
+                .any(|f| f.id == id && f.accepts_tab),
+            None => false,
+        }
+    }
+
+    /// Moves the focus to the nearest view in `dir` within the focus group
+    /// of the focused view. Returns false if the focused view isn't in a group,
+    /// or takes `Tab` or text input and so needs the arrow keys itself. The
+    /// arrow key then goes to the focused view instead.
+    fn focus_direction(&mut self, view: &impl View, dir: WorldOffset) -> bool {
+        let focused_id = match self.focused_id {
+            Some(id) => id,
+            None => return false,
+        };
+
+        let ids = self.focusables(view);
+        let current = match ids.iter().find(|f| f.id == focused_id) {
+            Some(f) if f.group.is_some() && !f.accepts_tab && !f.text_input => f,
+            _ => return false,
+        };
+
+        let from = current.rect.center();
+        let perp = WorldOffset::new(dir.y, -dir.x);
+
+        // Prefer views which are closer along the direction of travel
+        // and better aligned with the focused view.
+        let best = ids
+            .iter()
+            .filter(|f| f.id != focused_id && f.group == current.group)
+            .filter_map(|f| {
+                let d = f.rect.center() - from;
+                let along = d.dot(dir);
+                if along > 0.0 {
+                    Some((along + 2.0 * d.dot(perp).abs(), f.id))
+                } else {
+                    None
+                }
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        if let Some((_, id)) = best {
+            self.focus(id);
+        }
+
+        // Stay within the group even at its edges.
+        true
+    }
+
+    fn refresh_commands(&mut self, view: &impl View) {
+        let mut cmds = vec![];
+        self.commands(view, &mut cmds);
+        self.registered_commands = cmds;
+        self.commands_stale = false;
+    }
+
+    /// Finds the enabled command whose hotkey matches a key press with the current
+    /// modifiers. Keys for disabled commands go to the views instead. When hotkeys
+    /// conflict, the first command wins. Conflicts are reported once, when the app
+    /// starts.
+    fn hotkey_command(&self, key: &Key) -> Option<Arc<str>> {
+        self.registered_commands
+            .iter()
+            .filter(|cmd| cmd.enabled)
+            .find(|cmd| matches!(cmd.key, Some(k) if k.matches(key, &self.key_mods)))
+            .map(|cmd| cmd.path.clone())
+    }
+
     pub(crate) fn view_id(&mut self, path: &IdPath) -> ViewId {
         match self.view_ids.get_mut(path) {
             Some(id) => *id,
@@ -388,7 +855,8 @@ impl Context {
 
     pub(crate) fn set_dirty(&mut self) {
         if self.enable_dirty {
-            self.dirty = true
+            self.dirty = true;
+            self.commands_stale = true;
         }
     }
 
@@ -420,6 +888,44 @@ impl Context {
         });
     }
 
+    /// State of a view which keeps it itself rather than through `state`,
+    /// made with `func` if there's none of type `S` yet.
+    pub(crate) fn view_state<S: 'static>(
+        &mut self,
+        id: ViewId,
+        func: impl FnOnce() -> S,
+    ) -> &mut S {
+        let holder = self.state_map.entry(id).or_insert_with(|| StateHolder {
+            state: Box::new(()),
+            dirty: false,
+        });
+        if !holder.state.is::<S>() {
+            // Another kind of view used this id before.
+            holder.state = Box::new(func());
+        }
+        holder.state.downcast_mut::<S>().unwrap()
+    }
+
+    /// A value of the environment of the views being traversed.
+    pub(crate) fn get_env<S: Clone + Default + 'static>(&self) -> S {
+        self.env
+            .get(&TypeId::of::<S>())
+            .and_then(|b| b.downcast_ref::<S>())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The theme of the views being traversed.
+    pub(crate) fn theme(&self) -> Theme {
+        self.get_env()
+    }
+
+    /// Sets the theme for the whole window, including its background.
+    /// Use the `env` modifier to theme part of the UI.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.set_env(&theme);
+    }
+
     pub(crate) fn init_env<S: Clone + 'static, D: Fn() -> S + 'static>(&mut self, func: &D) -> S {
         self.env
             .entry(TypeId::of::<S>())
@@ -439,6 +945,33 @@ impl Context {
         old_value
     }
 
+    /// Puts back the value which `set_env` replaced.
+    pub(crate) fn restore_env<S: Clone + 'static>(&mut self, old: Option<S>) {
+        match old {
+            Some(value) => {
+                self.set_env(&value);
+            }
+            None => {
+                self.env.remove(&TypeId::of::<S>());
+            }
+        }
+    }
+
+    /// Applies the opacity of the views being drawn, from the `opacity`
+    /// modifier or a transition, to a color. A `canvas` applies it to its
+    /// paints and text already.
+    pub fn faded(&self, color: Color) -> Color {
+        Color {
+            a: color.a * self.opacity,
+            ..color
+        }
+    }
+
+    /// Whether the views being traversed are inside `disabled(true)`.
+    pub fn is_disabled(&self) -> bool {
+        self.get_env::<Disabled>().0
+    }
+
     pub fn get<S>(&self, id: StateHandle<S>) -> &S
     where
         S: 'static,
@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::sync::Arc;
 
 use crate::*;
@@ -30,8 +31,8 @@ pub enum Event {
     /// Called when the mouse gets outside the window
     MouseLeftWindow,
 
-    /// Menu command.
-    Command(Arc<str>),
+    /// Menu command, with an optional argument.
+    Command(Arc<str>, Option<CommandArg>),
 
     /// Key press.
     Key(Key),
@@ -39,8 +40,29 @@ pub enum Event {
     /// Key released.
     KeyReleased(Key),
 
-    /// Animation.
-    Anim,
+    /// Text typed by the user. Sent after the `Key` event for the same key press,
+    /// unless a control or command modifier is held.
+    TextInput(String),
+
+    /// The IME is composing text, which hasn't been committed yet.
+    ImePreedit {
+        text: String,
+        /// Byte range of the composition cursor within `text`, if it should be shown.
+        cursor: Option<(usize, usize)>,
+    },
+
+    /// The IME finished composing and the text should be inserted.
+    ImeCommit(String),
+
+    /// Animation frame.
+    Anim {
+        /// Seconds since the previous frame, or a nominal 1/60 s if the
+        /// previous frame didn't request another with `Context::request_frame`.
+        dt: f32,
+
+        /// Seconds on the context's clock.
+        time: f64,
+    },
 }
 
 impl Event {
@@ -54,6 +76,61 @@ impl Event {
         }
         event
     }
+
+    /// Maps the position, and the delta of a move, with `xform`.
+    pub fn transform(&self, xform: &LocalTransform) -> Event {
+        let mut event = self.clone();
+        match &mut event {
+            Event::TouchBegin { position, .. } => *position = xform.transform_point(*position),
+            Event::TouchMove {
+                position, delta, ..
+            } => {
+                *position = xform.transform_point(*position);
+                *delta = xform.transform_vector(*delta);
+            }
+            Event::TouchEnd { position, .. } => *position = xform.transform_point(*position),
+            _ => (),
+        }
+        event
+    }
+
+    /// Position of a touch event.
+    pub fn position(&self) -> Option<LocalPoint> {
+        match self {
+            Event::TouchBegin { position, .. }
+            | Event::TouchMove { position, .. }
+            | Event::TouchEnd { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+/// Argument passed along with a command, such as the file
+/// for an "Open Recent" command.
+#[derive(Clone)]
+pub struct CommandArg(Arc<dyn Any>);
+
+impl CommandArg {
+    pub fn new<T: 'static>(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Returns the value if it is of type `T`.
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl PartialEq for CommandArg {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl std::fmt::Debug for CommandArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CommandArg")
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
@@ -69,7 +146,7 @@ pub struct MouseButtons {
     pub middle: bool,
 }
 
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct KeyboardModifiers {
     pub shift: bool,
     pub control: bool,
@@ -77,13 +154,40 @@ pub struct KeyboardModifiers {
     pub command: bool,
 }
 
+impl KeyboardModifiers {
+    /// The modifier used for shortcuts on the current platform:
+    /// command on macOS, control everywhere else.
+    pub fn primary() -> Self {
+        if cfg!(target_os = "macos") {
+            Self {
+                command: true,
+                ..Default::default()
+            }
+        } else {
+            Self {
+                control: true,
+                ..Default::default()
+            }
+        }
+    }
+
+    /// Is the platform's shortcut modifier held?
+    pub fn has_primary(&self) -> bool {
+        if cfg!(target_os = "macos") {
+            self.command
+        } else {
+            self.control
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ElementState {
     Pressed,
     Released,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, Hash, PartialEq, Debug)]
 pub enum Key {
     Character(char),
 
@@ -115,7 +219,11 @@ pub enum Key {
     F12,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+/// Keyboard shortcut for a command.
+///
+/// All variants except `Chord` are triggered together with the
+/// platform-primary modifier (see `KeyboardModifiers::primary`).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Copy)]
 pub enum HotKey {
     KeyA,
     KeyB,
@@ -143,4 +251,183 @@ pub enum HotKey {
     KeyX,
     KeyY,
     KeyZ,
+    Key0,
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Key5,
+    Key6,
+    Key7,
+    Key8,
+    Key9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    Backspace,
+    Delete,
+    Enter,
+    Space,
+    Minus,
+    Equal,
+    BracketLeft,
+    BracketRight,
+    Backslash,
+    Semicolon,
+    Quote,
+    Comma,
+    Period,
+    Slash,
+    Backquote,
+
+    /// A key with an explicit set of modifiers.
+    ///
+    /// Keys are matched by the character they type, not by their position
+    /// on the keyboard, so a chord with shift must name the shifted
+    /// character: `Shift+1` is `Chord(Key::Character('!'), shift)` on a US
+    /// layout.
+    Chord(Key, KeyboardModifiers),
+}
+
+impl HotKey {
+    /// Uses an explicit set of modifiers instead of the platform-primary one.
+    pub fn with_modifiers(self, mods: KeyboardModifiers) -> HotKey {
+        HotKey::Chord(self.key(), mods)
+    }
+
+    /// The key which triggers the hotkey.
+    pub fn key(&self) -> Key {
+        use HotKey::*;
+        let c = match self {
+            KeyA => 'a',
+            KeyB => 'b',
+            KeyC => 'c',
+            KeyD => 'd',
+            KeyE => 'e',
+            KeyF => 'f',
+            KeyG => 'g',
+            KeyH => 'h',
+            KeyI => 'i',
+            KeyJ => 'j',
+            KeyK => 'k',
+            KeyL => 'l',
+            KeyM => 'm',
+            KeyN => 'n',
+            KeyO => 'o',
+            KeyP => 'p',
+            KeyQ => 'q',
+            KeyR => 'r',
+            KeyS => 's',
+            KeyT => 't',
+            KeyU => 'u',
+            KeyV => 'v',
+            KeyW => 'w',
+            KeyX => 'x',
+            KeyY => 'y',
+            KeyZ => 'z',
+            Key0 => '0',
+            Key1 => '1',
+            Key2 => '2',
+            Key3 => '3',
+            Key4 => '4',
+            Key5 => '5',
+            Key6 => '6',
+            Key7 => '7',
+            Key8 => '8',
+            Key9 => '9',
+            Minus => '-',
+            Equal => '=',
+            BracketLeft => '[',
+            BracketRight => ']',
+            Backslash => '\\',
+            Semicolon => ';',
+            Quote => '\'',
+            Comma => ',',
+            Period => '.',
+            Slash => '/',
+            Backquote => '`',
+            F1 => return Key::F1,
+            F2 => return Key::F2,
+            F3 => return Key::F3,
+            F4 => return Key::F4,
+            F5 => return Key::F5,
+            F6 => return Key::F6,
+            F7 => return Key::F7,
+            F8 => return Key::F8,
+            F9 => return Key::F9,
+            F10 => return Key::F10,
+            F11 => return Key::F11,
+            F12 => return Key::F12,
+            ArrowDown => return Key::ArrowDown,
+            ArrowLeft => return Key::ArrowLeft,
+            ArrowRight => return Key::ArrowRight,
+            ArrowUp => return Key::ArrowUp,
+            Backspace => return Key::Backspace,
+            Delete => return Key::Delete,
+            Enter => return Key::Enter,
+            Space => return Key::Space,
+            Chord(key, _) => return *key,
+        };
+        Key::Character(c)
+    }
+
+    /// The modifiers which must be held to trigger the hotkey.
+    pub fn modifiers(&self) -> KeyboardModifiers {
+        match self {
+            HotKey::Chord(_, mods) => *mods,
+            _ => KeyboardModifiers::primary(),
+        }
+    }
+
+    /// Does a key press with the given modifiers trigger this hotkey?
+    /// Letters match in either case, but other shifted characters only
+    /// match themselves (see `Chord`).
+    pub fn matches(&self, key: &Key, mods: &KeyboardModifiers) -> bool {
+        normalize_key(self.key()) == normalize_key(*key) && self.modifiers() == *mods
+    }
+}
+
+impl std::fmt::Display for HotKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mods = self.modifiers();
+        if mods.control {
+            write!(f, "Ctrl+")?;
+        }
+        if mods.alt {
+            write!(f, "Alt+")?;
+        }
+        if mods.shift {
+            write!(f, "Shift+")?;
+        }
+        if mods.command {
+            write!(f, "Cmd+")?;
+        }
+        match self.key() {
+            Key::Character(c) => write!(f, "{}", c.to_ascii_uppercase()),
+            key => write!(f, "{:?}", key),
+        }
+    }
+}
+
+/// Letters are compared without regard to case, since shift
+/// is part of the modifiers.
+fn normalize_key(key: Key) -> Key {
+    match key {
+        Key::Character(c) => Key::Character(c.to_ascii_lowercase()),
+        Key::Space => Key::Character(' '),
+        _ => key,
+    }
 }
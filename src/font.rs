@@ -0,0 +1,701 @@
+use crate::*;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use ttf_parser::{name_id, Face, OutlineBuilder};
+
+type TextBoundsFn<'a> = &'a mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect;
+
+/// How far synthesized italics lean, horizontally per unit of height.
+const ITALIC_SLANT: f32 = 0.2;
+
+/// Weight of a font, from 100 (thin) to 900 (black).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const REGULAR: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const SEMIBOLD: FontWeight = FontWeight(600);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const BLACK: FontWeight = FontWeight(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::REGULAR
+    }
+}
+
+/// Which fonts text is drawn with.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FontFamily {
+    /// The font built into rui.
+    #[default]
+    Default,
+
+    /// A registered monospaced font. Without one, the built-in font is
+    /// spaced out so that each character takes the width of a digit, which
+    /// keeps numeric readouts from jittering.
+    Monospace,
+
+    /// Fonts registered under a family name, compared ignoring case.
+    Named(String),
+}
+
+impl FontFamily {
+    pub fn named(name: &str) -> Self {
+        Self::Named(name.into())
+    }
+}
+
+impl From<&str> for FontFamily {
+    fn from(name: &str) -> Self {
+        Self::named(name)
+    }
+}
+
+/// Font used to draw text.
+///
+/// Set it for a subtree with the `env` modifier. `Text` and `Span` can
+/// override parts of it, which are otherwise inherited.
+///
+/// ```no_run
+/// # use rui::*;
+/// let family = register_font_file("Brand-Regular.otf").unwrap();
+/// let ui = vstack((
+///     text("Welcome"),
+///     text("12.50").monospaced(),
+/// ))
+/// .env(Font::new(family));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Font {
+    pub family: FontFamily,
+    pub weight: FontWeight,
+    pub italic: bool,
+}
+
+impl Font {
+    pub fn new(family: impl Into<FontFamily>) -> Self {
+        Self {
+            family: family.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn monospace() -> Self {
+        Self::new(FontFamily::Monospace)
+    }
+
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+}
+
+/// Parts of a `Font` set on a view or span. The rest comes from the environment.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FontAttributes {
+    pub family: Option<FontFamily>,
+    pub weight: Option<FontWeight>,
+    pub italic: Option<bool>,
+}
+
+impl FontAttributes {
+    pub fn set(&mut self, font: Font) {
+        self.family = Some(font.family);
+        self.weight = Some(font.weight);
+        self.italic = Some(font.italic);
+    }
+
+    pub fn resolve(&self, inherited: &Font) -> Font {
+        Font {
+            family: self.family.clone().unwrap_or(inherited.family.clone()),
+            weight: self.weight.unwrap_or(inherited.weight),
+            italic: self.italic.unwrap_or(inherited.italic),
+        }
+    }
+}
+
+/// Error from registering a font.
+#[derive(Debug)]
+pub enum FontError {
+    Io(std::io::Error),
+    Parse(ttf_parser::FaceParsingError),
+}
+
+impl std::fmt::Display for FontError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read font: {}", err),
+            Self::Parse(err) => write!(f, "couldn't parse font: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+impl From<std::io::Error> for FontError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A registered font face. Collections have one for each font.
+pub(crate) struct FontFace {
+    /// Parsed when registered. Fonts are never unregistered, so
+    /// their data lives until the program exits.
+    face: Face<'static>,
+    family: String,
+    weight: FontWeight,
+    italic: bool,
+    monospaced: bool,
+}
+
+/// Fonts in the order they were registered, which is also the order
+/// missing characters are looked up in. A font's id is its index.
+static FONTS: Mutex<Vec<Arc<FontFace>>> = Mutex::new(Vec::new());
+
+/// Calls a function with the registered fonts, without copying the registry.
+fn with_fonts<T>(f: impl FnOnce(&[Arc<FontFace>]) -> T) -> T {
+    f(&FONTS.lock().unwrap())
+}
+
+fn family_name(face: &Face) -> String {
+    let name = |id| {
+        face.names()
+            .into_iter()
+            .filter(|n| n.name_id == id)
+            .find_map(|n| n.to_string())
+    };
+    name(name_id::TYPOGRAPHIC_FAMILY)
+        .or_else(|| name(name_id::FAMILY))
+        .unwrap_or_default()
+}
+
+/// Registers a TrueType or OpenType font, or each font of a collection,
+/// so text can use it. Returns the family of the (first) font.
+pub fn register_font(data: Vec<u8>) -> Result<FontFamily, FontError> {
+    let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+    for index in 0..count {
+        Face::parse(&data, index).map_err(FontError::Parse)?;
+    }
+
+    let data: &'static [u8] = Box::leak(data.into_boxed_slice());
+    let mut faces = vec![];
+    for index in 0..count {
+        let face = Face::parse(data, index).map_err(FontError::Parse)?;
+        faces.push(Arc::new(FontFace {
+            family: family_name(&face),
+            weight: FontWeight(face.weight().to_number()),
+            italic: face.is_italic() || face.is_oblique(),
+            monospaced: face.is_monospaced(),
+            face,
+        }));
+    }
+
+    let family = FontFamily::Named(faces[0].family.clone());
+    FONTS.lock().unwrap().extend(faces);
+    Ok(family)
+}
+
+/// Reads and registers a font file. See `register_font`.
+pub fn register_font_file(path: impl AsRef<Path>) -> Result<FontFamily, FontError> {
+    register_font(std::fs::read(path)?)
+}
+
+/// The registered face closest to a font, or `None` for the built-in font.
+fn select_face(font: &Font, fonts: &[Arc<FontFace>]) -> Option<Arc<FontFace>> {
+    fonts
+        .iter()
+        .filter(|f| match &font.family {
+            FontFamily::Default => false,
+            FontFamily::Monospace => f.monospaced,
+            FontFamily::Named(name) => f.family.eq_ignore_ascii_case(name),
+        })
+        .min_by_key(|f| (f.italic != font.italic, f.weight.0.abs_diff(font.weight.0)))
+        .cloned()
+}
+
+/// Splits text into runs drawn with the same face. Characters missing from
+/// the font are taken from the other registered fonts, in order, and then
+/// from the built-in font (`None`).
+pub(crate) fn font_runs(text: &str, font: &Font) -> Vec<(Range<usize>, Option<Arc<FontFace>>)> {
+    with_fonts(|fonts| {
+        let primary = match select_face(font, fonts) {
+            Some(primary) => primary,
+            None => return vec![(0..text.len(), None)],
+        };
+        let fallbacks: Vec<&Arc<FontFace>> = std::iter::once(&primary)
+            .chain(fonts.iter().filter(|f| !Arc::ptr_eq(f, &primary)))
+            .collect();
+        split_runs(text, &fallbacks)
+    })
+}
+
+fn split_runs(text: &str, faces: &[&Arc<FontFace>]) -> Vec<(Range<usize>, Option<Arc<FontFace>>)> {
+    let mut runs: Vec<(Range<usize>, Option<Arc<FontFace>>)> = vec![];
+    for (i, c) in text.char_indices() {
+        let face = faces
+            .iter()
+            .find(|f| f.face.glyph_index(c).is_some())
+            .map(|f| (*f).clone());
+        let end = i + c.len_utf8();
+        match runs.last_mut() {
+            // Spaces continue the current run rather than splitting it.
+            Some((range, last)) if c == ' ' || same_face(last, &face) => range.end = end,
+            _ => runs.push((i..end, face)),
+        }
+    }
+    runs
+}
+
+fn same_face(a: &Option<Arc<FontFace>>, b: &Option<Arc<FontFace>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Advance of text in the built-in font. Unlike the bounds, this includes
+/// trailing whitespace.
+fn builtin_advance(text: &str, size: u32, text_bounds: TextBoundsFn) -> f32 {
+    if text.is_empty() {
+        return 0.0;
+    }
+    let marked = text_bounds(&format!("{}H", text), size, None).max_x();
+    (marked - text_bounds("H", size, None).max_x()).max(0.0)
+}
+
+/// Width of each character of the built-in font in monospace.
+fn monospace_cell(size: u32, text_bounds: TextBoundsFn) -> f32 {
+    builtin_advance("0", size, text_bounds)
+}
+
+/// Distance the pen moves when drawing text, including trailing whitespace,
+/// with extra space after each character.
+pub(crate) fn text_advance(
+    text: &str,
+    size: u32,
+    font: &Font,
+    letter_spacing: f32,
+    text_bounds: TextBoundsFn,
+) -> f32 {
+    let mut width = letter_spacing * text.chars().count() as f32;
+    for (range, face) in font_runs(text, font) {
+        let run = &text[range];
+        width += match face {
+            Some(face) => {
+                let face = &face.face;
+                let scale = size as f32 / face.units_per_em() as f32;
+                run.chars()
+                    .filter_map(|c| face.glyph_hor_advance(face.glyph_index(c)?))
+                    .map(|a| a as f32 * scale)
+                    .sum()
+            }
+            None if font.family == FontFamily::Monospace => {
+                run.chars().count() as f32 * monospace_cell(size, text_bounds)
+            }
+            None => builtin_advance(run, size, text_bounds),
+        };
+    }
+    width
+}
+
+/// Distance from the top of a line to the baseline: the height of capitals.
+pub(crate) fn text_ascent(size: u32, font: &Font, text_bounds: TextBoundsFn) -> f32 {
+    match with_fonts(|fonts| select_face(font, fonts)) {
+        Some(face) => {
+            let face = &face.face;
+            let h = face
+                .glyph_index('H')
+                .and_then(|g| face.glyph_bounding_box(g));
+            let height = face
+                .capital_height()
+                .or(h.map(|r| r.y_max))
+                .unwrap_or(face.ascender());
+            height as f32 * size as f32 / face.units_per_em() as f32
+        }
+        // Fonts are laid out with the top of the line at zero, and
+        // "H" sits on the baseline.
+        None => -text_bounds("H", size, None).min_y(),
+    }
+}
+
+/// Receives the quadratic segments of glyph outlines.
+trait QuadSink {
+    fn move_to(&mut self, p: LocalPoint);
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint);
+}
+
+impl QuadSink for Vger {
+    fn move_to(&mut self, p: LocalPoint) {
+        Vger::move_to(self, p)
+    }
+
+    fn quad_to(&mut self, b: LocalPoint, c: LocalPoint) {
+        Vger::quad_to(self, b, c)
+    }
+}
+
+/// Turns glyph outlines into path segments, which are all quadratic.
+struct GlyphPath<'a, S: QuadSink> {
+    sink: &'a mut S,
+    origin: LocalPoint,
+    scale: f32,
+    slant: f32,
+    start: LocalPoint,
+    pen: LocalPoint,
+}
+
+impl<S: QuadSink> GlyphPath<'_, S> {
+    fn point(&self, x: f32, y: f32) -> LocalPoint {
+        let (x, y) = (x * self.scale, y * self.scale);
+        self.origin + LocalOffset::new(x + y * self.slant, y)
+    }
+
+    fn quad(&mut self, b: LocalPoint, c: LocalPoint) {
+        self.sink.quad_to(b, c);
+        self.pen = c;
+    }
+
+    fn line(&mut self, p: LocalPoint) {
+        self.quad(self.pen.lerp(p, 0.5), p);
+    }
+}
+
+impl<S: QuadSink> OutlineBuilder for GlyphPath<'_, S> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.sink.move_to(p);
+        self.start = p;
+        self.pen = p;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.line(p);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (b, c) = (self.point(x1, y1), self.point(x, y));
+        self.quad(b, c);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // Split the cubic in half, and approximate each half with a quadratic.
+        let (p0, p1, p2, p3) = (
+            self.pen,
+            self.point(x1, y1),
+            self.point(x2, y2),
+            self.point(x, y),
+        );
+        let (a, b, c) = (p0.lerp(p1, 0.5), p1.lerp(p2, 0.5), p2.lerp(p3, 0.5));
+        let (d, e) = (a.lerp(b, 0.5), b.lerp(c, 0.5));
+        let mid = d.lerp(e, 0.5);
+        let control = |p0: LocalPoint, p1: LocalPoint, p2: LocalPoint, p3: LocalPoint| {
+            ((p1.to_vector() + p2.to_vector()) * 0.75 - (p0.to_vector() + p3.to_vector()) * 0.25)
+                .to_point()
+        };
+        self.quad(control(p0, a, d, mid), mid);
+        self.quad(control(mid, e, c, p3), p3);
+    }
+
+    fn close(&mut self) {
+        if self.pen != self.start {
+            self.line(self.start);
+        }
+    }
+}
+
+/// How far text in a face leans to look italic, when it isn't already.
+fn synthesized_slant(font: &Font, face: &FontFace) -> f32 {
+    if font.italic && !face.italic {
+        ITALIC_SLANT
+    } else {
+        0.0
+    }
+}
+
+/// Is text in a face thickened to look bold, when it isn't already?
+fn synthesized_bold(font: &Font, face: Option<&FontFace>) -> bool {
+    font.weight >= FontWeight::SEMIBOLD && face.is_none_or(|f| f.weight < FontWeight::SEMIBOLD)
+}
+
+/// Adds a glyph's outline to a path. Returns false if it has none, like a space.
+fn outline_glyph(
+    sink: &mut impl QuadSink,
+    face: &FontFace,
+    glyph: ttf_parser::GlyphId,
+    origin: LocalPoint,
+    scale: f32,
+    slant: f32,
+) -> bool {
+    let mut path = GlyphPath {
+        sink,
+        origin,
+        scale,
+        slant,
+        start: LocalPoint::zero(),
+        pen: LocalPoint::zero(),
+    };
+    face.face.outline_glyph(glyph, &mut path).is_some()
+}
+
+/// Draws glyph outlines from a registered face. Returns the new pen position.
+#[allow(clippy::too_many_arguments)]
+fn draw_face_run(
+    vger: &mut Vger,
+    text: &str,
+    face: &FontFace,
+    size: u32,
+    paint: PaintIndex,
+    mut x: f32,
+    baseline: f32,
+    font: &Font,
+    letter_spacing: f32,
+) -> f32 {
+    let parsed = &face.face;
+    let scale = size as f32 / parsed.units_per_em() as f32;
+    let slant = synthesized_slant(font, face);
+    for c in text.chars() {
+        if let Some(glyph) = parsed.glyph_index(c) {
+            if outline_glyph(vger, face, glyph, [x, baseline].into(), scale, slant) {
+                vger.fill(paint);
+            }
+            x += parsed.glyph_hor_advance(glyph).unwrap_or(0) as f32 * scale;
+        }
+        x += letter_spacing;
+    }
+    x
+}
+
+/// Draws a run of the built-in font. Returns the new pen position.
+#[allow(clippy::too_many_arguments)]
+fn draw_builtin_run(
+    vger: &mut Vger,
+    text: &str,
+    size: u32,
+    color: Color,
+    x: f32,
+    baseline: f32,
+    font: &Font,
+    letter_spacing: f32,
+) -> f32 {
+    let y = baseline - vger.text_bounds("H", size, None).min_y();
+    let width = builtin_advance(text, size, &mut |t, s, w| vger.text_bounds(t, s, w))
+        + letter_spacing * text.chars().count() as f32;
+
+    if font.family == FontFamily::Monospace {
+        let cell = monospace_cell(size, &mut |t, s, w| vger.text_bounds(t, s, w));
+        for (i, c) in text.chars().enumerate() {
+            let c = c.to_string();
+            let advance = builtin_advance(&c, size, &mut |t, s, w| vger.text_bounds(t, s, w));
+            let centered = (cell - advance) / 2.0;
+            vger.save();
+            vger.translate([x + i as f32 * (cell + letter_spacing) + centered, y]);
+            vger.text(&c, size, color, None);
+            vger.restore();
+        }
+        return x + text.chars().count() as f32 * (cell + letter_spacing);
+    }
+
+    if letter_spacing == 0.0 {
+        vger.save();
+        vger.translate([x, y]);
+        vger.text(text, size, color, None);
+        vger.restore();
+        return x + width;
+    }
+
+    let positions = vger.glyph_positions(text, size, None);
+    for (i, (c, r)) in text.chars().zip(positions).enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let c = c.to_string();
+        let bearing = vger.text_bounds(&c, size, None).min_x();
+        vger.save();
+        vger.translate([x + r.origin.x - bearing + i as f32 * letter_spacing, y]);
+        vger.text(&c, size, color, None);
+        vger.restore();
+    }
+    x + width
+}
+
+/// Draws text with its baseline starting at a point.
+///
+/// Bold text in a font without a bold face is thickened by drawing it twice.
+pub(crate) fn draw_text(
+    vger: &mut Vger,
+    text: &str,
+    size: u32,
+    color: Color,
+    font: &Font,
+    origin: LocalPoint,
+    letter_spacing: f32,
+) {
+    let paint = vger.color_paint(color);
+    let mut x = origin.x;
+    for (range, face) in font_runs(text, font) {
+        let run = &text[range];
+        let bold = synthesized_bold(font, face.as_deref());
+        let passes: &[f32] = if bold { &[0.0, 1.0] } else { &[0.0] };
+        let mut end = x;
+        for pass in passes {
+            let offset = pass * (size as f32 / 36.0).max(0.5);
+            end = match &face {
+                Some(face) => draw_face_run(
+                    vger,
+                    run,
+                    face,
+                    size,
+                    paint,
+                    x + offset,
+                    origin.y,
+                    font,
+                    letter_spacing,
+                ),
+                None => draw_builtin_run(
+                    vger,
+                    run,
+                    size,
+                    color,
+                    x + offset,
+                    origin.y,
+                    font,
+                    letter_spacing,
+                ),
+            } - offset;
+        }
+        x = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::views::paragraph::tests::bounds;
+
+    #[test]
+    fn test_font_attributes() {
+        let inherited = Font::new("Inter").italic();
+        let attrs = FontAttributes {
+            weight: Some(FontWeight::BOLD),
+            ..Default::default()
+        };
+        assert_eq!(
+            attrs.resolve(&inherited),
+            Font::new("Inter").bold().italic()
+        );
+
+        let mut attrs = FontAttributes::default();
+        attrs.set(Font::monospace());
+        assert_eq!(attrs.resolve(&inherited), Font::monospace());
+    }
+
+    #[test]
+    fn test_builtin_monospace() {
+        // Without registered fonts, each character takes the width of a digit.
+        let size = 20;
+        let mono = text_advance("1.5", size, &Font::monospace(), 0.0, &mut bounds);
+        assert_eq!(mono, 30.0);
+        let spaced = text_advance("1.5", size, &Font::monospace(), 1.0, &mut bounds);
+        assert_eq!(spaced, 33.0);
+        assert_eq!(font_runs("1.5", &Font::new("Missing")).len(), 1);
+    }
+
+    /// A font whose only glyph is an "A", 540 units wide in an em of 1000.
+    fn demo_font() -> Font {
+        static FAMILY: std::sync::OnceLock<FontFamily> = std::sync::OnceLock::new();
+        let family = FAMILY.get_or_init(|| {
+            register_font(include_bytes!("../tests/fonts/demo.ttf").to_vec()).unwrap()
+        });
+        Font::new(family.clone())
+    }
+
+    impl QuadSink for Vec<LocalPoint> {
+        fn move_to(&mut self, p: LocalPoint) {
+            self.push(p)
+        }
+
+        fn quad_to(&mut self, _b: LocalPoint, c: LocalPoint) {
+            self.push(c)
+        }
+    }
+
+    #[test]
+    fn test_registered_font() {
+        let font = demo_font();
+
+        // "B" falls back to the built-in font.
+        let runs = font_runs("AB", &font);
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].1.is_some() && runs[1].1.is_none());
+
+        assert_eq!(text_advance("A", 100, &font, 0.0, &mut bounds), 54.0);
+        assert_eq!(text_advance("AB", 100, &font, 1.0, &mut bounds), 106.0);
+    }
+
+    #[test]
+    fn test_glyph_outline() {
+        let font = demo_font();
+        let face = with_fonts(|fonts| select_face(&font, fonts)).unwrap();
+        let glyph = face.face.glyph_index('A').unwrap();
+        // The first contour is the triangular hole, closed by a line back to the start.
+        let triangle = [[17.3, 26.7], [36.9, 26.7], [27.0, 58.7], [17.3, 26.7]];
+
+        let outline = |slant| {
+            let mut points: Vec<LocalPoint> = vec![];
+            assert!(outline_glyph(
+                &mut points,
+                &face,
+                glyph,
+                LocalPoint::zero(),
+                0.1,
+                slant
+            ));
+            points
+        };
+        let close = |a: LocalPoint, b: [f32; 2]| (a - LocalPoint::from(b)).length() < 1e-3;
+
+        let regular = outline(synthesized_slant(&font, &face));
+        assert!(!synthesized_bold(&font, Some(&face)));
+        // Then the outside, with 8 points.
+        assert_eq!(regular.len(), triangle.len() + 9);
+        assert!(regular.iter().zip(triangle).all(|(a, b)| close(*a, b)));
+
+        // The face is neither bold nor italic, so both are synthesized.
+        let styled = font.bold().italic();
+        assert!(synthesized_bold(&styled, Some(&face)));
+        let slanted = outline(synthesized_slant(&styled, &face));
+        let leaned = triangle.map(|[x, y]| [x + y * ITALIC_SLANT, y]);
+        assert!(slanted.iter().zip(leaned).all(|(a, b)| close(*a, b)));
+    }
+
+    #[test]
+    fn test_register_invalid_font() {
+        assert!(matches!(
+            register_font(b"not a font".to_vec()),
+            Err(FontError::Parse(_))
+        ));
+        assert!(matches!(
+            register_font_file("/nonexistent/font.ttf"),
+            Err(FontError::Io(_))
+        ));
+    }
+}
@@ -41,12 +41,30 @@ pub use modifiers::*;
 mod colors;
 pub use colors::*;
 
+mod font;
+pub use font::*;
+
+mod theme;
+pub use theme::*;
+
 mod align;
 pub use align::*;
 
 mod region;
 pub use region::*;
 
+mod clipboard;
+pub use clipboard::*;
+
+mod clock;
+pub use clock::*;
+
+mod animation;
+pub use animation::*;
+
+mod undo;
+pub use undo::*;
+
 #[cfg(feature = "winit")]
 mod winit_event_loop;
 
@@ -1,6 +1,8 @@
 use crate::*;
 use accesskit::Role;
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Modifiers common to all views.
 pub trait Modifiers: View + Sized {
@@ -9,26 +11,68 @@ pub trait Modifiers: View + Sized {
         AnimView::new(self, func)
     }
 
+    /// Animates changes of the offset and size the view is laid out with,
+    /// for example when a `list` is reordered. Put it on the children of
+    /// a stack or list.
+    fn animate_layout(self, animation: Animation) -> LayoutAnimation<Self> {
+        LayoutAnimation::new(self, animation)
+    }
+
     /// Puts a view behind another. The background view inherits the size of the view.
     fn background<BG: View + Clone>(self, background: BG) -> Background<Self, BG> {
         Background::new(self, background)
     }
 
+    /// Outlines a view, inside its bounds.
+    fn border(self, color: Color, width: f32) -> Border<Self> {
+        Border::new(self, color, width)
+    }
+
+    /// Sets the style of the buttons in this subtree.
+    fn button_style(self, style: impl ButtonStyle) -> SetenvView<Self, AnyButtonStyle> {
+        self.env(AnyButtonStyle(Arc::new(style)))
+    }
+
     /// Adds a menu command.
     fn command<F: Fn(&mut Context) + Clone + 'static>(
         self,
         name: &str,
         key: Option<HotKey>,
         f: F,
-    ) -> Command<Self, F> {
-        Command::new(self, name.into(), key, f)
+    ) -> Command<Self, CommandFunc<F>> {
+        Command::new(self, name.into(), key, CommandFunc { f })
+    }
+
+    /// Adds a menu command which takes an argument of type `T`,
+    /// passed with `Event::Command`.
+    fn command_arg<T: 'static, F: Fn(&mut Context, &T) + Clone + 'static>(
+        self,
+        name: &str,
+        key: Option<HotKey>,
+        f: F,
+    ) -> Command<Self, CommandArgFunc<F, T>> {
+        Command::new(
+            self,
+            name.into(),
+            key,
+            CommandArgFunc {
+                f,
+                phantom: PhantomData,
+            },
+        )
     }
 
-    /// Adds a group of menu commands.
+    /// Adds a group of menu commands, which are always enabled.
     fn command_group<T: CommandTuple>(self, cmds: T) -> CommandGroup<Self, T> {
         CommandGroup::new(self, cmds)
     }
 
+    /// Stops the views in this subtree from responding to input and dims
+    /// them. Doesn't re-enable views inside a disabled subtree.
+    fn disabled(self, disabled: bool) -> DisabledView<Self> {
+        DisabledView::new(self, disabled)
+    }
+
     /// Calls a function in response to a drag.
     fn drag<
         F: Fn(&mut Context, LocalOffset, GestureState, Option<MouseButton>) + Clone + 'static,
@@ -90,6 +134,34 @@ pub trait Modifiers: View + Sized {
         SetenvView::new(self, value)
     }
 
+    /// Sets the order in which `Tab` visits the focusable views in this subtree.
+    /// Lower orders come first, and views of equal order are visited in tree order.
+    /// The default order is 0.
+    fn focus_order(self, order: i32) -> FocusTraversal<Self> {
+        FocusTraversal::new(self, Some(order), true, false, false)
+    }
+
+    /// Allows or prevents `Tab` from moving the focus to views in this subtree.
+    /// They can still be focused by clicking.
+    fn focusable(self, focusable: bool) -> FocusTraversal<Self> {
+        FocusTraversal::new(self, None, focusable, false, false)
+    }
+
+    /// Groups the focusable views in this subtree for arrow-key navigation.
+    /// When a view in the group has the focus, the arrow keys move the focus
+    /// to the nearest view of the group in that direction. Views which edit
+    /// text or accept `Tab` get the arrow keys instead.
+    fn focus_group(self) -> FocusTraversal<Self> {
+        FocusTraversal::new(self, None, true, true, false)
+    }
+
+    /// Lets the focusable views in this subtree receive `Tab`, for example to
+    /// indent text, instead of `Tab` moving the focus away from them.
+    /// `Escape` releases the focus, after which `Tab` moves it again.
+    fn accepts_tab(self) -> FocusTraversal<Self> {
+        FocusTraversal::new(self, None, true, false, true)
+    }
+
     /// Indicates that this item can expand within a stack.
     fn flex(self) -> Flex<Self> {
         Flex::new(self)
@@ -119,11 +191,35 @@ pub trait Modifiers: View + Sized {
         KeyView::new_released(self, f)
     }
 
+    /// Responds to text typed by the user, including text composed with an IME.
+    ///
+    /// Unlike `key`, this receives whole strings, so dead keys, emoji
+    /// and CJK input arrive intact.
+    fn text_input<F: Fn(&mut Context, TextInputEvent) + Clone + 'static>(
+        self,
+        f: F,
+    ) -> TextInputView<Self, F> {
+        TextInputView::new(self, f)
+    }
+
+    /// When this view replaces a removed view with the same `id` and
+    /// `namespace`, it moves and scales from where that view was to its own
+    /// bounds, using the animation of `with_animation` or a spring.
+    fn matched_geometry(self, id: impl Hash, namespace: impl Hash) -> MatchedGeometry<Self> {
+        MatchedGeometry::new(self, id, namespace)
+    }
+
     /// Applies an offset to the view in local space.
     fn offset<Off: Into<LocalOffset>>(self, offset: Off) -> Offset<Self> {
         Offset::new(self, offset.into())
     }
 
+    /// Fades the view and its subtree. Nested opacities multiply, and
+    /// overlapping views in the subtree show through each other.
+    fn opacity(self, alpha: f32) -> Opacity<Self> {
+        Opacity::new(self, alpha)
+    }
+
     /// Adds space around a view. Can be either `Auto` or `Px(number_of_pixels)`
     fn padding(self, param: impl Into<PaddingParam>) -> Padding<Self> {
         Padding::new(self, param.into())
@@ -134,11 +230,37 @@ pub trait Modifiers: View + Sized {
         RoleView::new(self, role)
     }
 
+    /// Rotates the view by `angle` radians about `anchor`, which is relative
+    /// to the view's size, so `[0.5, 0.5]` is its center.
+    fn rotate(self, angle: f32, anchor: impl Into<LocalPoint>) -> TransformView<Self> {
+        TransformView::new(
+            self,
+            LocalTransform::rotation(euclid::Angle::radians(angle)),
+            anchor.into(),
+        )
+    }
+
+    /// Scales the view about `anchor`, which is relative to the view's size,
+    /// so `[0.5, 0.5]` is its center.
+    fn scale(self, sx: f32, sy: f32, anchor: impl Into<LocalPoint>) -> TransformView<Self> {
+        TransformView::new(self, LocalTransform::scale(sx, sy), anchor.into())
+    }
+
+    /// Casts a shadow under a view, blurred by `radius` and moved by `offset`.
+    fn shadow(self, color: Color, radius: f32, offset: impl Into<LocalOffset>) -> Shadow<Self> {
+        Shadow::new(self, color, radius, offset.into())
+    }
+
     /// Constrains the size of a view.
     fn size<Sz: Into<LocalSize>>(self, size: Sz) -> Size<Self> {
         Size::new(self, size.into())
     }
 
+    /// Sets the style of the sliders in this subtree.
+    fn slider_style(self, style: impl SliderStyle) -> SetenvView<Self, AnySliderStyle> {
+        self.env(AnySliderStyle(Arc::new(style)))
+    }
+
     /// Calls a function in response to a tap.
     fn tap<A: 'static, F: Fn(&mut Context) -> A + Clone + 'static>(
         self,
@@ -186,6 +308,27 @@ pub trait Modifiers: View + Sized {
         Touch::new(self, TouchFunc { f })
     }
 
+    /// Sets the style of the toggles in this subtree.
+    fn toggle_style(self, style: impl ToggleStyle) -> SetenvView<Self, AnyToggleStyle> {
+        self.env(AnyToggleStyle(Arc::new(style)))
+    }
+
+    /// Applies a transform to the view, about its origin. Touches and hit
+    /// testing go through the inverse of the transform.
+    fn transform(self, xform: LocalTransform) -> TransformView<Self> {
+        TransformView::new(self, xform, LocalPoint::zero())
+    }
+
+    /// Animates the view in when it's added to the tree and out when it's
+    /// removed, for example from a `cond` or `list`.
+    ///
+    /// A view which is leaving is drawn on top of all the others, wherever
+    /// it was among its siblings. Transitions inside it leave along with it,
+    /// without running their own.
+    fn transition(self, transition: Transition) -> TransitionView<Self> {
+        TransitionView::new(self, transition)
+    }
+
     /// Specify the title of the window.
     fn window_title(self, title: &str) -> TitleView<Self> {
         TitleView::new(self, title)
@@ -16,6 +16,40 @@ pub enum Paint {
 }
 
 impl Paint {
+    /// The paint shown by the view with `id`. Solid colors animate in
+    /// `with_animation`.
+    pub(crate) fn animated(&self, id: ViewId, cx: &mut Context) -> Paint {
+        match self {
+            Paint::Color(color) => Paint::Color(cx.animated(id, *color)),
+            _ => self.clone(),
+        }
+    }
+
+    /// The paint with its colors faded by `cx.faded`.
+    pub(crate) fn faded(&self, cx: &Context) -> Paint {
+        match self {
+            Paint::Color(color) => Paint::Color(cx.faded(*color)),
+            Paint::Gradient {
+                start,
+                end,
+                inner_color,
+                outer_color,
+            } => Paint::Gradient {
+                start: *start,
+                end: *end,
+                inner_color: cx.faded(*inner_color),
+                outer_color: cx.faded(*outer_color),
+            },
+        }
+    }
+
+    /// Advances the animation of a solid color. Call it on `Event::Anim`.
+    pub(crate) fn step(&self, id: ViewId, cx: &mut Context, dt: f32) {
+        if let Paint::Color(color) = self {
+            cx.step_animated(id, *color, dt);
+        }
+    }
+
     pub fn vger_paint(&self, vger: &mut Vger) -> PaintIndex {
         match self {
             Paint::Color(color) => vger.color_paint(*color),
@@ -28,3 +62,9 @@ impl Paint {
         }
     }
 }
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Color(color)
+    }
+}
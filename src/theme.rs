@@ -0,0 +1,271 @@
+use crate::*;
+
+/// Colors of the built-in views.
+///
+/// Set it for a subtree with the `env` modifier, or for the whole window,
+/// including its background, with `Context::set_theme`. Read it in your own
+/// views with `env(|theme: Theme, cx| ...)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Window background.
+    pub background: Color,
+    pub text: Color,
+
+    /// Placeholders and hints.
+    pub secondary_text: Color,
+
+    /// Slider thumbs, knob arcs and the focus ring.
+    pub accent: Color,
+
+    /// Selections and the border of a focused text field.
+    pub accent_dark: Color,
+
+    /// The filled part of sliders and a toggle which is on.
+    pub accent_background: Color,
+
+    /// Text fields in an error state.
+    pub error: Color,
+
+    /// Text fields, knobs and a toggle which is off.
+    pub control_background: Color,
+    pub button_background: Color,
+    pub button_hover: Color,
+    pub button_down: Color,
+
+    /// The groove of a slider.
+    pub track: Color,
+
+    /// The knob of a toggle.
+    pub thumb: Color,
+    pub caret: Color,
+}
+
+impl Theme {
+    /// The default theme.
+    pub fn dark() -> Self {
+        Self {
+            background: BLACK,
+            text: TEXT_COLOR,
+            secondary_text: MEDIUM_GRAY,
+            accent: AZURE_HIGHLIGHT,
+            accent_dark: AZURE_HIGHLIGHT_DARK,
+            accent_background: AZURE_HIGHLIGHT_BACKGROUND,
+            error: RED_HIGHLIGHT,
+            control_background: CONTROL_BACKGROUND,
+            button_background: BUTTON_BACKGROUND_COLOR,
+            button_hover: BUTTON_HOVER_COLOR,
+            button_down: BUTTON_DOWN_COLOR,
+            track: BUTTON_BACKGROUND_COLOR,
+            thumb: MEDIUM_GRAY,
+            caret: Color::MAGENTA,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            background: Color::hex_const("#F2F2F2"),
+            text: Color::hex_const("#1C1C1E"),
+            secondary_text: Color::hex_const("#8A8A8E"),
+            accent: Color::hex_const("#0091B0"),
+            accent_dark: Color::hex_const("#7FD6EA"),
+            accent_background: Color::hex_const("#BDEBF5"),
+            error: Color::hex_const("#D70050"),
+            control_background: Color::hex_const("#E3E3E6"),
+            button_background: Color::hex_const("#DADADD"),
+            button_hover: Color::hex_const("#CDCDD1"),
+            button_down: Color::hex_const("#BCBCC1"),
+            track: Color::hex_const("#D1D1D6"),
+            thumb: WHITE,
+            caret: Color::hex_const("#0091B0"),
+        }
+    }
+
+    /// Colors of disabled views, faded halfway into the background.
+    pub fn dimmed(&self) -> Self {
+        let fade = |color: Color| color.mix(self.background, 0.5);
+        Self {
+            background: self.background,
+            text: fade(self.text),
+            secondary_text: fade(self.secondary_text),
+            accent: fade(self.accent),
+            accent_dark: fade(self.accent_dark),
+            accent_background: fade(self.accent_background),
+            error: fade(self.error),
+            control_background: fade(self.control_background),
+            button_background: fade(self.button_background),
+            button_hover: fade(self.button_hover),
+            button_down: fade(self.button_down),
+            track: fade(self.track),
+            thumb: fade(self.thumb),
+            caret: fade(self.caret),
+        }
+    }
+
+    /// Sets a color by the name of its field. Returns false for an unknown name.
+    pub fn set_color(&mut self, name: &str, color: Color) -> bool {
+        let field = match name {
+            "background" => &mut self.background,
+            "text" => &mut self.text,
+            "secondary_text" => &mut self.secondary_text,
+            "accent" => &mut self.accent,
+            "accent_dark" => &mut self.accent_dark,
+            "accent_background" => &mut self.accent_background,
+            "error" => &mut self.error,
+            "control_background" => &mut self.control_background,
+            "button_background" => &mut self.button_background,
+            "button_hover" => &mut self.button_hover,
+            "button_down" => &mut self.button_down,
+            "track" => &mut self.track,
+            "thumb" => &mut self.thumb,
+            "caret" => &mut self.caret,
+            _ => return false,
+        };
+        *field = color;
+        true
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Error from loading a theme file.
+#[cfg(feature = "theme-files")]
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(std::io::Error),
+
+    /// The file isn't valid TOML or JSON, or isn't a table of strings.
+    Parse(String),
+
+    /// A key which isn't a color of `Theme`, or an unknown base theme.
+    UnknownKey(String),
+
+    /// A color which isn't in `#RRGGBB` or `#RRGGBBAA` form.
+    InvalidColor {
+        name: String,
+        value: String,
+    },
+}
+
+#[cfg(feature = "theme-files")]
+impl std::fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read theme: {}", err),
+            Self::Parse(err) => write!(f, "couldn't parse theme: {}", err),
+            Self::UnknownKey(key) => write!(f, "unknown theme key: {}", key),
+            Self::InvalidColor { name, value } => {
+                write!(f, "invalid color for {}: {}", name, value)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "theme-files")]
+impl std::error::Error for ThemeError {}
+
+#[cfg(feature = "theme-files")]
+impl Theme {
+    /// Builds a theme from color names and hex values. The `base` key
+    /// picks the theme ("dark" or "light") which supplies missing colors.
+    fn from_table(table: std::collections::BTreeMap<String, String>) -> Result<Self, ThemeError> {
+        let mut theme = match table.get("base").map(|b| b.as_str()) {
+            None | Some("dark") => Theme::dark(),
+            Some("light") => Theme::light(),
+            Some(base) => return Err(ThemeError::UnknownKey(base.into())),
+        };
+        for (name, value) in table.iter().filter(|(k, _)| *k != "base") {
+            let color = Color::hex(value).map_err(|_| ThemeError::InvalidColor {
+                name: name.clone(),
+                value: value.clone(),
+            })?;
+            if !theme.set_color(name, color) {
+                return Err(ThemeError::UnknownKey(name.clone()));
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Parses a theme from TOML, for example:
+    ///
+    /// ```toml
+    /// base = "light"
+    /// accent = "#FF6600"
+    /// button_background = "#FFE0CC"
+    /// ```
+    pub fn from_toml(source: &str) -> Result<Self, ThemeError> {
+        Self::from_table(toml::from_str(source).map_err(|e| ThemeError::Parse(e.to_string()))?)
+    }
+
+    /// Parses a theme from a JSON object, with the same keys as `from_toml`.
+    pub fn from_json(source: &str) -> Result<Self, ThemeError> {
+        Self::from_table(
+            serde_json::from_str(source).map_err(|e| ThemeError::Parse(e.to_string()))?,
+        )
+    }
+
+    /// Loads a theme file, as JSON if its extension is `.json` and otherwise as TOML.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(ThemeError::Io)?;
+        if path.extension().is_some_and(|e| e == "json") {
+            Self::from_json(&source)
+        } else {
+            Self::from_toml(&source)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::views::paragraph::tests::bounds;
+
+    #[test]
+    fn test_text_color_from_theme() {
+        let mut cx = Context::new();
+        let ui = vstack((text("themed"), text("red").color(RED_HIGHLIGHT))).env(Theme::light());
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut bounds,
+            },
+        );
+
+        let color = |cx: &mut Context, i| {
+            let id = cx.view_id(&vec![0, 0, i]);
+            stored_paragraph(id, cx).unwrap().spans[0].color
+        };
+        assert_eq!(color(&mut cx, 0), Some(Theme::light().text));
+        assert_eq!(color(&mut cx, 1), Some(RED_HIGHLIGHT));
+    }
+
+    #[cfg(feature = "theme-files")]
+    #[test]
+    fn test_theme_files() {
+        let theme = Theme::from_toml("base = \"light\"\naccent = \"#FF6600\"").unwrap();
+        assert_eq!(theme.accent, Color::hex_const("#FF6600"));
+        assert_eq!(theme.text, Theme::light().text);
+
+        let theme = Theme::from_json(r##"{"error": "#00FF0080"}"##).unwrap();
+        assert_eq!(theme.error, Color::new(0.0, 1.0, 0.0, 128.0 / 255.0));
+        assert_eq!(theme.text, Theme::dark().text);
+
+        assert!(matches!(
+            Theme::from_toml("acent = \"#FF6600\""),
+            Err(ThemeError::UnknownKey(_))
+        ));
+        assert!(matches!(
+            Theme::from_json(r#"{"accent": "orange"}"#),
+            Err(ThemeError::InvalidColor { .. })
+        ));
+    }
+}
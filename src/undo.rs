@@ -0,0 +1,354 @@
+use crate::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type UndoFn = Box<dyn Fn(&mut Context)>;
+
+/// Records a `get_mut` write once it's done, if it changed the value.
+pub(crate) type PendingUndo = Box<dyn FnOnce(&mut Context)>;
+
+/// An undoable step, made of all the changes recorded while it was open.
+struct Transaction {
+    name: String,
+    undo: Vec<UndoFn>,
+    redo: Vec<UndoFn>,
+}
+
+impl Transaction {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+}
+
+#[derive(Default)]
+struct UndoStack {
+    undo: Vec<Transaction>,
+    redo: Vec<Transaction>,
+
+    /// Transaction being recorded, and how many groups are open.
+    open: Option<Transaction>,
+    depth: usize,
+}
+
+/// History of changes which can be undone and redone.
+///
+/// Found in the environment, so a subtree can be given its own history with
+/// `.env(UndoManager::new())`. Writes through bindings made with `undoable`
+/// are recorded automatically. Changes recorded between `begin_group` and
+/// `end_group` undo as a single step, which is how a slider drag undoes at once.
+///
+/// Cloning an `UndoManager` gives another handle to the same history.
+#[derive(Clone, Default)]
+pub struct UndoManager {
+    stack: Rc<RefCell<UndoStack>>,
+}
+
+impl UndoManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a transaction. Groups nest, and only the outermost group's name is kept.
+    /// If the name is empty, the name of the first recorded change is used.
+    pub fn begin_group(&self, name: &str) {
+        let mut stack = self.stack.borrow_mut();
+        if stack.depth == 0 {
+            stack.open = Some(Transaction::new(name));
+        }
+        stack.depth += 1;
+    }
+
+    /// Ends a transaction started with `begin_group`.
+    pub fn end_group(&self) {
+        let mut stack = self.stack.borrow_mut();
+        if stack.depth == 0 {
+            log::warn!("UndoManager::end_group called without begin_group");
+            return;
+        }
+        stack.depth -= 1;
+        if stack.depth == 0 {
+            if let Some(t) = stack.open.take() {
+                if !t.undo.is_empty() {
+                    stack.undo.push(t);
+                }
+            }
+        }
+    }
+
+    /// Records a change, given functions which revert and reapply it.
+    pub fn record(
+        &self,
+        name: &str,
+        undo: impl Fn(&mut Context) + 'static,
+        redo: impl Fn(&mut Context) + 'static,
+    ) {
+        let mut stack = self.stack.borrow_mut();
+        stack.redo.clear();
+        match &mut stack.open {
+            Some(t) => {
+                if t.name.is_empty() {
+                    t.name = name.into();
+                }
+                t.undo.push(Box::new(undo));
+                t.redo.push(Box::new(redo));
+            }
+            None => {
+                let mut t = Transaction::new(name);
+                t.undo.push(Box::new(undo));
+                t.redo.push(Box::new(redo));
+                stack.undo.push(t);
+            }
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.stack.borrow().undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.stack.borrow().redo.is_empty()
+    }
+
+    /// Name of the transaction `undo` would revert, for an "Undo ..." menu item.
+    pub fn undo_name(&self) -> Option<String> {
+        self.stack.borrow().undo.last().map(|t| t.name.clone())
+    }
+
+    /// Name of the transaction `redo` would reapply.
+    pub fn redo_name(&self) -> Option<String> {
+        self.stack.borrow().redo.last().map(|t| t.name.clone())
+    }
+
+    /// Reverts the latest transaction. Returns false if there was nothing to undo.
+    pub fn undo(&self, cx: &mut Context) -> bool {
+        cx.flush_undo();
+        // Release the borrow while running the changes, since they may record.
+        let t = self.stack.borrow_mut().undo.pop();
+        match t {
+            Some(t) => {
+                self.replay(cx, t.undo.iter().rev());
+                self.stack.borrow_mut().redo.push(t);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reapplies the latest undone transaction. Returns false if there was nothing to redo.
+    pub fn redo(&self, cx: &mut Context) -> bool {
+        cx.flush_undo();
+        let t = self.stack.borrow_mut().redo.pop();
+        match t {
+            Some(t) => {
+                self.replay(cx, t.redo.iter());
+                self.stack.borrow_mut().undo.push(t);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all history.
+    pub fn clear(&self) {
+        let mut stack = self.stack.borrow_mut();
+        stack.undo.clear();
+        stack.redo.clear();
+    }
+
+    fn replay<'a>(&self, cx: &mut Context, changes: impl Iterator<Item = &'a UndoFn>) {
+        // Don't record the writes made while replaying.
+        let recording = std::mem::replace(&mut cx.record_undo, false);
+        for f in changes {
+            f(cx);
+        }
+        cx.record_undo = recording;
+    }
+}
+
+/// Binding which records writes made with `with_mut` in the `UndoManager`.
+#[derive(Clone, Copy)]
+pub struct Undoable<B> {
+    binding: B,
+    name: &'static str,
+}
+
+impl<S, B> Binding<S> for Undoable<B>
+where
+    B: Binding<S>,
+    S: Clone + PartialEq + 'static,
+{
+    fn get<'a>(&self, cx: &'a Context) -> &'a S {
+        self.binding.get(cx)
+    }
+
+    /// The write is recorded after the current event, or before
+    /// the next write through an undoable binding.
+    fn get_mut<'a>(&self, cx: &'a mut Context) -> &'a mut S {
+        cx.flush_undo();
+        if cx.record_undo {
+            let old = self.binding.get(cx).clone();
+            let binding = self.binding;
+            let name = self.name;
+            let undo = cx.undo_manager();
+            cx.pending_undo.push(Box::new(move |cx| {
+                record_change(cx, &undo, binding, name, old)
+            }));
+        }
+        self.binding.get_mut(cx)
+    }
+
+    fn with_mut<T>(&self, cx: &mut Context, f: impl FnOnce(&mut S) -> T) -> T {
+        cx.flush_undo();
+        let old = self.binding.get(cx).clone();
+        let result = self.binding.with_mut(cx, f);
+        if cx.record_undo {
+            let undo = cx.undo_manager();
+            record_change(cx, &undo, self.binding, self.name, old);
+        }
+        result
+    }
+}
+
+/// Records a write to `binding` if it changed the value from `old`.
+fn record_change<S, B>(cx: &Context, undo: &UndoManager, binding: B, name: &str, old: S)
+where
+    B: Binding<S>,
+    S: Clone + PartialEq + 'static,
+{
+    let new = binding.get(cx);
+    if *new != old {
+        let new = new.clone();
+        undo.record(
+            name,
+            move |cx| binding.with_mut(cx, |v| *v = old.clone()),
+            move |cx| binding.with_mut(cx, |v| *v = new.clone()),
+        );
+    }
+}
+
+/// Wraps a binding so changes made through it can be undone.
+///
+/// `name` describes the change, for example "Volume".
+pub fn undoable<S>(binding: impl Binding<S>, name: &'static str) -> impl Binding<S>
+where
+    S: Clone + PartialEq + 'static,
+{
+    Undoable { binding, name }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_undo_manager() {
+        let mut cx = Context::new();
+        let id = ViewId::default();
+        cx.init_state(id, &|| 0);
+        let value = undoable(StateHandle::<i32>::new(id), "Value");
+        let set = setter(value);
+
+        set(1, &mut cx);
+
+        // A group undoes as one step.
+        let undo = cx.undo_manager();
+        undo.begin_group("Drag");
+        for i in 2..5 {
+            set(i, &mut cx);
+        }
+        undo.end_group();
+
+        // Writes which don't change the value aren't recorded.
+        set(4, &mut cx);
+
+        assert_eq!(undo.undo_name().as_deref(), Some("Drag"));
+        assert!(undo.undo(&mut cx));
+        assert_eq!(*value.get(&cx), 1);
+        assert_eq!(undo.undo_name().as_deref(), Some("Value"));
+        assert!(undo.undo(&mut cx));
+        assert_eq!(*value.get(&cx), 0);
+        assert!(!undo.undo(&mut cx));
+
+        assert!(undo.redo(&mut cx));
+        assert!(undo.redo(&mut cx));
+        assert_eq!(*value.get(&cx), 4);
+        assert!(!undo.can_redo());
+
+        // A new change discards the redo history.
+        undo.undo(&mut cx);
+        set(7, &mut cx);
+        assert!(!undo.can_redo());
+        undo.undo(&mut cx);
+        assert_eq!(*value.get(&cx), 1);
+    }
+
+    #[test]
+    fn test_undoable_get_mut() {
+        let mut cx = Context::new();
+        let id = ViewId::default();
+        cx.init_state(id, &|| 0);
+        let value = undoable(StateHandle::<i32>::new(id), "Value");
+
+        *value.get_mut(&mut cx) = 1;
+        *value.get_mut(&mut cx) += 0;
+        *value.get_mut(&mut cx) = 2;
+
+        let undo = cx.undo_manager();
+        assert!(undo.undo(&mut cx));
+        assert_eq!(*value.get(&cx), 1);
+        assert!(undo.undo(&mut cx));
+        assert_eq!(*value.get(&cx), 0);
+        assert!(!undo.can_undo());
+    }
+
+    #[test]
+    fn test_drag_undo_group() {
+        let mut cx = Context::new();
+
+        // The dragged view goes away before the touch ends.
+        let ui = state(
+            || 0,
+            |count, _| {
+                state(
+                    || true,
+                    move |shown, cx| {
+                        let value = undoable(count, "Value");
+                        cond(
+                            cx[shown],
+                            rectangle().drag(move |cx, _, _, _| {
+                                value.with_mut(cx, |v| *v += 1);
+                                cx[shown] = false;
+                            }),
+                            EmptyView {},
+                        )
+                    },
+                )
+            },
+        );
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let count = StateHandle::<i32>::new(cx.view_id(&path));
+
+        let position = [50.0, 50.0].into();
+        cx.process(&ui, &Event::TouchBegin { id: 0, position });
+        cx.process(&ui, &Event::TouchEnd { id: 0, position });
+        assert_eq!(cx[count], 1);
+
+        // The group was closed, so the drag can be undone.
+        let undo = cx.undo_manager();
+        assert!(undo.undo(&mut cx));
+        assert_eq!(cx[count], 0);
+    }
+}
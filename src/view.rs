@@ -1,6 +1,6 @@
 use crate::*;
-use std::any::{Any, TypeId};
 use dyn_clone::DynClone;
+use std::any::{Any, TypeId};
 
 pub struct DrawArgs<'a> {
     pub cx: &'a mut Context,
@@ -41,6 +41,16 @@ pub trait DynView: private::Sealed + DynClone + 'static {
     /// Determines dirty regions which need repainting.
     fn dirty(&self, _path: &mut IdPath, _xform: LocalToWorld, _cx: &mut Context) {}
 
+    /// Accumulates the views which can take the keyboard focus, in tree order.
+    fn focusables(
+        &self,
+        _path: &mut IdPath,
+        _xform: LocalToWorld,
+        _cx: &mut Context,
+        _ids: &mut Vec<FocusInfo>,
+    ) {
+    }
+
     /// Draws the view using vger.
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs);
 
@@ -88,4 +98,4 @@ pub trait DynView: private::Sealed + DynClone + 'static {
 /// Trait for the unit of UI composition.
 pub trait View: DynView + Clone {}
 
-impl<V: DynView + Clone> View for V {}
\ No newline at end of file
+impl<V: DynView + Clone> View for V {}
@@ -29,8 +29,8 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        if let Event::Anim = event {
-            (self.func)(cx, 1.0 / 60.0) // XXX: assume 60fps for now.
+        if let Event::Anim { dt, .. } = event {
+            (self.func)(cx, *dt)
         }
 
         path.push(0);
@@ -70,6 +70,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         map.push(cx.view_id(path));
         path.push(0);
@@ -80,6 +80,18 @@ impl DynView for AnyView {
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(self.id_hash());
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(self.id_hash());
         self.child.gc(path, cx, map);
@@ -72,6 +72,21 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+        path.push(1);
+        self.background.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(0);
         self.child.gc(path, cx, map);
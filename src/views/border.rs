@@ -0,0 +1,171 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `border` modifier.
+#[derive(Clone)]
+pub struct Border<V> {
+    child: V,
+    color: Color,
+    width: f32,
+    corner_radius: f32,
+}
+
+impl<V> Border<V>
+where
+    V: View,
+{
+    pub fn new(child: V, color: Color, width: f32) -> Self {
+        Self {
+            child,
+            color,
+            width,
+            corner_radius: 0.0,
+        }
+    }
+
+    /// Rounds the corners of the border, to match a rounded view.
+    pub fn corner_radius(self, corner_radius: f32) -> Self {
+        Self {
+            corner_radius,
+            ..self
+        }
+    }
+}
+
+impl<V> DynView for Border<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        // Drawn over the child, inside its bounds.
+        let inset = self.width / 2.0;
+        let rect = args.cx.get_layout(path).rect.inflate(-inset, -inset);
+        let paint = args.vger.color_paint(args.cx.faded(self.color));
+        args.vger.stroke_rect(
+            rect.min(),
+            rect.max(),
+            (self.corner_radius - inset).max(0.0),
+            self.width,
+            paint,
+        );
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for Border<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_border() {
+        let mut cx = Context::new();
+
+        let ui = rectangle()
+            .border(BLACK, 2.0)
+            .shadow(BLACK, 4.0, [0.0, -2.0])
+            .size([40.0, 20.0]);
+
+        let mut path = vec![0];
+        let sz = ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        assert_eq!(sz, [40.0, 20.0].into());
+
+        // Both record the child's size, and touches go through to it.
+        assert_eq!(cx.get_layout(&vec![0, 0]).rect.size, sz);
+        assert_eq!(cx.get_layout(&vec![0, 0, 0]).rect.size, sz);
+        let rect_id = cx.view_id(&vec![0, 0, 0, 0]);
+        assert_eq!(
+            ui.hittest(&mut path, [1.0, 1.0].into(), &mut cx),
+            Some(rect_id)
+        );
+    }
+}
@@ -9,45 +9,79 @@ struct ButtonState {
     down: bool,
 }
 
-/// Calls a function when the button is tapped.
+/// The built-in look of buttons: a rounded rectangle behind the label.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultButtonStyle;
+
+impl ButtonStyle for DefaultButtonStyle {
+    fn body(&self, config: ButtonConfiguration, theme: &Theme) -> AnyView {
+        let state = config.state;
+        let color = if state.pressed {
+            theme.button_down
+        } else if state.hovered {
+            theme.button_hover
+        } else {
+            theme.button_background
+        };
+        any_view(
+            config
+                .label
+                .padding(Auto)
+                .background(rectangle().corner_radius(BUTTON_CORNER_RADIUS).color(color)),
+        )
+    }
+}
+
+/// Calls a function when the button is tapped, or when Space or Enter
+/// is pressed while it has the focus. Tapping doesn't take the focus.
+///
+/// Its look comes from the `ButtonStyle` set with `button_style`.
 pub fn button<A: 'static, F: Fn(&mut Context) -> A + 'static + Clone>(
     view: impl View,
     f: F,
 ) -> impl View {
-    state(
-        || ButtonState::default(),
-        move |s, cx| {
-            let f = f.clone();
-            view.clone()
-                .padding(Auto)
-                .background(
-                    rectangle()
-                        .corner_radius(BUTTON_CORNER_RADIUS)
-                        .color(if cx[s].down {
-                            BUTTON_DOWN_COLOR
-                        } else if cx[s].hovered {
-                            BUTTON_HOVER_COLOR
-                        } else {
-                            BUTTON_BACKGROUND_COLOR
-                        }),
-                )
-                .touch(move |cx, info| match info.state {
-                    TouchState::Begin => {
-                        cx[s].down = true;
-                    }
-                    TouchState::End => {
-                        cx[s].down = false;
-                        if cx[s].hovered {
-                            f(cx);
+    keyboard_focus(move |has_focus| {
+        let f = f.clone();
+        let view = view.clone();
+        state(
+            || ButtonState::default(),
+            move |s, cx| {
+                let f = f.clone();
+                let key_f = f.clone();
+                let config = ButtonConfiguration {
+                    label: any_view(view.clone()),
+                    state: ControlState {
+                        hovered: cx[s].hovered,
+                        pressed: cx[s].down,
+                        focused: has_focus,
+                        disabled: cx.is_disabled(),
+                    },
+                };
+                cx.get_env::<AnyButtonStyle>()
+                    .body(config, &cx.theme())
+                    .touch(move |cx, info| match info.state {
+                        TouchState::Begin => {
+                            cx[s].down = true;
                         }
-                    }
-                })
-                .hover(move |cx, inside| {
-                    cx[s].hovered = inside;
-                })
-                .role(Role::Button)
-        },
-    )
+                        TouchState::End => {
+                            cx[s].down = false;
+                            if cx[s].hovered && !cx.is_disabled() {
+                                f(cx);
+                            }
+                        }
+                    })
+                    .hover(move |cx, inside| {
+                        cx[s].hovered = inside;
+                    })
+                    .key(move |cx, key| {
+                        if has_focus && matches!(key, Key::Space | Key::Enter) {
+                            key_f(cx);
+                        }
+                    })
+                    .role(Role::Button)
+            },
+        )
+    })
 }
 
 /// Version of button which emits an action directly instead of taking a callback.
@@ -80,23 +114,24 @@ mod tests {
             &mut LayoutArgs {
                 sz,
                 cx: &mut cx,
-                text_bounds: &mut |_, _, _| LocalRect::new(LocalPoint::zero(), [90.0, 90.0].into()),
+                text_bounds: &mut crate::views::paragraph::tests::bounds,
             },
         );
         assert!(path.len() == 1);
 
-        assert_eq!(button_sz, sz);
+        // The label's line, with padding.
+        assert_eq!(button_sz, [64.0, 31.6].into());
         let s = StateHandle::<bool>::new(cx.view_id(&path));
         assert!(!*s.get(&cx));
 
         let events = [
             Event::TouchBegin {
                 id: 0,
-                position: [50.0, 50.0].into(),
+                position: [30.0, 15.0].into(),
             },
             Event::TouchEnd {
                 id: 0,
-                position: [50.0, 50.0].into(),
+                position: [30.0, 15.0].into(),
             },
         ];
 
@@ -111,4 +146,33 @@ mod tests {
         // State should have changed.
         assert!(*s.get(&cx));
     }
+
+    #[test]
+    fn test_click_keeps_focus() {
+        let mut cx = Context::new();
+
+        let ui = button("button", |_| ());
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut crate::views::paragraph::tests::bounds,
+            },
+        );
+
+        // Clicking leaves the focus with, say, a text field.
+        let field = cx.view_id(&vec![1]);
+        cx.focus(field);
+        let position = [30.0, 15.0].into();
+        cx.process(&ui, &Event::TouchBegin { id: 0, position });
+        cx.process(&ui, &Event::TouchEnd { id: 0, position });
+        assert_eq!(cx.focused_id, Some(field));
+
+        // Tab still reaches the button.
+        let button = cx.view_id(&vec![0]);
+        cx.process(&ui, &Event::Key(Key::Tab));
+        assert_eq!(cx.focused_id, Some(button));
+    }
 }
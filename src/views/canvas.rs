@@ -1,4 +1,61 @@
 use crate::*;
+use std::ops::{Deref, DerefMut};
+
+/// The `Vger` a `canvas` draws with. Its paints and text are faded by
+/// the opacity of the views being drawn, as with `Context::faded`.
+pub struct CanvasVger<'a> {
+    vger: &'a mut Vger,
+    opacity: f32,
+}
+
+impl CanvasVger<'_> {
+    fn faded(&self, color: Color) -> Color {
+        Color {
+            a: color.a * self.opacity,
+            ..color
+        }
+    }
+
+    /// Solid color paint.
+    pub fn color_paint(&mut self, color: Color) -> PaintIndex {
+        let color = self.faded(color);
+        self.vger.color_paint(color)
+    }
+
+    /// Linear gradient paint.
+    pub fn linear_gradient<Pt: Into<LocalPoint>>(
+        &mut self,
+        start: Pt,
+        end: Pt,
+        inner_color: Color,
+        outer_color: Color,
+        glow: f32,
+    ) -> PaintIndex {
+        let (inner_color, outer_color) = (self.faded(inner_color), self.faded(outer_color));
+        self.vger
+            .linear_gradient(start, end, inner_color, outer_color, glow)
+    }
+
+    /// Renders text.
+    pub fn text(&mut self, text: &str, size: u32, color: Color, max_width: Option<f32>) {
+        let color = self.faded(color);
+        self.vger.text(text, size, color, max_width)
+    }
+}
+
+impl Deref for CanvasVger<'_> {
+    type Target = Vger;
+
+    fn deref(&self) -> &Vger {
+        self.vger
+    }
+}
+
+impl DerefMut for CanvasVger<'_> {
+    fn deref_mut(&mut self) -> &mut Vger {
+        self.vger
+    }
+}
 
 /// Struct for `canvas`
 #[derive(Clone)]
@@ -8,13 +65,17 @@ pub struct Canvas<F> {
 
 impl<F> DynView for Canvas<F>
 where
-    F: Fn(&mut Context, LocalRect, &mut Vger) + Clone + 'static,
+    F: Fn(&mut Context, LocalRect, &mut CanvasVger) + Clone + 'static,
 {
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
         let rect = args.cx.get_layout(path).rect;
 
         args.vger.save();
-        (self.func)(args.cx, rect, args.vger);
+        let mut vger = CanvasVger {
+            vger: args.vger,
+            opacity: args.cx.opacity,
+        };
+        (self.func)(args.cx, rect, &mut vger);
         args.vger.restore();
     }
 
@@ -45,7 +106,11 @@ where
 }
 
 /// Canvas for GPU drawing with Vger. See https://github.com/audulus/vger-rs.
-pub fn canvas<F: Fn(&mut Context, LocalRect, &mut Vger) + Clone + 'static>(f: F) -> Canvas<F> {
+/// Colors are faded by the `opacity` modifier and transitions, except
+/// those drawn by functions which take the `Vger` itself.
+pub fn canvas<F: Fn(&mut Context, LocalRect, &mut CanvasVger) + Clone + 'static>(
+    f: F,
+) -> Canvas<F> {
     Canvas { func: f }
 }
 
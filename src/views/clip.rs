@@ -81,6 +81,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         map.push(cx.view_id(path));
         path.push(0);
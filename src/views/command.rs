@@ -1,18 +1,65 @@
 use crate::*;
+use std::marker::PhantomData;
 use std::{any::Any, sync::Arc};
 
+pub trait CommandFn: Clone {
+    fn call(&self, cx: &mut Context, name: &str, arg: Option<&CommandArg>);
+}
+
+#[derive(Clone)]
+pub struct CommandFunc<F> {
+    pub f: F,
+}
+
+impl<F: Fn(&mut Context) + Clone + 'static> CommandFn for CommandFunc<F> {
+    fn call(&self, cx: &mut Context, _name: &str, _arg: Option<&CommandArg>) {
+        (self.f)(cx)
+    }
+}
+
+pub struct CommandArgFunc<F, T> {
+    pub f: F,
+    pub phantom: PhantomData<T>,
+}
+
+// Explicit Clone impl so T doesn't need to be Clone.
+impl<F: Clone, T> Clone for CommandArgFunc<F, T> {
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F: Fn(&mut Context, &T) + Clone + 'static, T: 'static> CommandFn for CommandArgFunc<F, T> {
+    fn call(&self, cx: &mut Context, name: &str, arg: Option<&CommandArg>) {
+        match arg.and_then(|a| a.get::<T>()) {
+            Some(a) => (self.f)(cx, a),
+            None => log::warn!(
+                "command {:?} ignored: expected an argument of type {}",
+                name,
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+}
+
+/// Struct for the `command` modifier.
 #[derive(Clone)]
-pub struct Command<V, F> {
+pub struct Command<V, F, E = fn(&Context) -> bool, C = fn(&Context) -> bool> {
     child: V,
     name: Arc<str>,
     key: Option<HotKey>,
     func: F,
+    enabled: E,
+    checked: C,
 }
 
 impl<V, F> Command<V, F>
 where
     V: View,
-    F: Fn(&mut Context) + Clone + 'static,
+    F: CommandFn + 'static,
 {
     pub fn new(v: V, name: Arc<str>, key: Option<HotKey>, f: F) -> Self {
         Self {
@@ -20,14 +67,57 @@ where
             name,
             key,
             func: f,
+            enabled: |_| true,
+            checked: |_| false,
         }
     }
 }
 
-impl<V, F> DynView for Command<V, F>
+impl<V, F, E, C> Command<V, F, E, C>
 where
     V: View,
-    F: Fn(&mut Context) + Clone + 'static,
+    F: CommandFn + 'static,
+    E: Fn(&Context) -> bool + Clone + 'static,
+    C: Fn(&Context) -> bool + Clone + 'static,
+{
+    /// Only allows the command to run when `enabled` returns true.
+    /// Menus and palettes show disabled commands greyed out.
+    pub fn enabled<E2: Fn(&Context) -> bool + Clone + 'static>(
+        self,
+        enabled: E2,
+    ) -> Command<V, F, E2, C> {
+        Command {
+            child: self.child,
+            name: self.name,
+            key: self.key,
+            func: self.func,
+            enabled,
+            checked: self.checked,
+        }
+    }
+
+    /// Shows a check mark next to the command when `checked` returns true.
+    pub fn checked<C2: Fn(&Context) -> bool + Clone + 'static>(
+        self,
+        checked: C2,
+    ) -> Command<V, F, E, C2> {
+        Command {
+            child: self.child,
+            name: self.name,
+            key: self.key,
+            func: self.func,
+            enabled: self.enabled,
+            checked,
+        }
+    }
+}
+
+impl<V, F, E, C> DynView for Command<V, F, E, C>
+where
+    V: View,
+    F: CommandFn + 'static,
+    E: Fn(&Context) -> bool + Clone + 'static,
+    C: Fn(&Context) -> bool + Clone + 'static,
 {
     fn process(
         &self,
@@ -36,9 +126,13 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        if let Event::Command(name) = &event {
+        if let Event::Command(name, arg) = &event {
             if *name == self.name {
-                (self.func)(cx);
+                if (self.enabled)(cx) {
+                    self.func.call(cx, name, arg.as_ref());
+                } else {
+                    log::info!("command {:?} ignored: it is disabled", name);
+                }
             }
         }
         path.push(0);
@@ -73,9 +167,23 @@ where
         cmds.push(CommandInfo {
             path: self.name.clone(),
             key: self.key,
+            enabled: (self.enabled)(cx),
+            checked: (self.checked)(cx),
         })
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(0);
         self.child.gc(path, cx, map);
@@ -93,7 +201,7 @@ where
     }
 }
 
-impl<V, F> private::Sealed for Command<V, F> {}
+impl<V, F, E, C> private::Sealed for Command<V, F, E, C> {}
 
 pub trait DynCommandBase {
     fn exec(&self);
@@ -242,6 +350,10 @@ impl<
     }
 }
 
+/// Struct for the `command_group` modifier.
+///
+/// Commands in a group have no predicates, so they are always enabled and
+/// never checked. Use the `command` modifier for commands which need them.
 #[derive(Clone)]
 pub struct CommandGroup<V, C> {
     child: V,
@@ -270,7 +382,7 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        if let Event::Command(name) = &event {
+        if let Event::Command(name, _) = &event {
             self.cmds.foreach_cmd(&mut |cmd| {
                 if cmd.name() == *name {
                     cmd.exec();
@@ -310,10 +422,24 @@ where
             cmds.push(CommandInfo {
                 path: cmd.name(),
                 key: cmd.key(),
+                enabled: true,
+                checked: false,
             })
         });
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(0);
         self.child.gc(path, cx, map);
@@ -355,7 +481,7 @@ impl DynCommandBase for NullCommand {
         self.name.clone()
     }
     fn key(&self) -> Option<HotKey> {
-        None
+        self.key
     }
 }
 
@@ -412,3 +538,192 @@ where
         }
     }
 }
+
+/// Finds pairs of commands which are bound to the same hotkey.
+///
+/// Returns the shared hotkey and the names of both commands.
+pub fn hotkey_conflicts(cmds: &[CommandInfo]) -> Vec<(HotKey, Arc<str>, Arc<str>)> {
+    let mut conflicts = vec![];
+    for (i, a) in cmds.iter().enumerate() {
+        for b in &cmds[i + 1..] {
+            if let (Some(ka), Some(kb)) = (a.key, b.key) {
+                if a.path != b.path && ka.matches(&kb.key(), &kb.modifiers()) {
+                    conflicts.push((ka, a.path.clone(), b.path.clone()));
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_hotkey_dispatch() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |count, _| {
+                rectangle()
+                    .command("File:New", Some(HotKey::KeyN), move |cx| cx[count] += 1)
+                    .command(
+                        "Edit:Redo",
+                        Some(HotKey::KeyZ.with_modifiers(KeyboardModifiers {
+                            shift: true,
+                            ..KeyboardModifiers::primary()
+                        })),
+                        move |cx| cx[count] += 10,
+                    )
+            },
+        );
+
+        let s = StateHandle::<i32>::new(cx.view_id(&vec![0]));
+
+        // No modifiers, so the key press isn't a hotkey.
+        cx.process(&ui, &Event::Key(Key::Character('n')));
+        assert_eq!(cx[s], 0);
+
+        cx.key_mods = KeyboardModifiers::primary();
+        cx.process(&ui, &Event::Key(Key::Character('n')));
+        assert_eq!(cx[s], 1);
+
+        cx.key_mods.shift = true;
+        cx.process(&ui, &Event::Key(Key::Character('Z')));
+        assert_eq!(cx[s], 11);
+
+        // Commands are only gathered again after the tree changes.
+        assert!(!cx.commands_stale);
+        cx[s] = 0;
+        assert!(cx.commands_stale);
+        cx.process(&ui, &Event::Key(Key::Character('x')));
+        assert!(!cx.commands_stale);
+    }
+
+    #[test]
+    fn test_shifted_hotkey() {
+        let shift = KeyboardModifiers {
+            shift: true,
+            ..Default::default()
+        };
+        assert!(HotKey::KeyA
+            .with_modifiers(shift)
+            .matches(&Key::Character('A'), &shift));
+
+        // Other shifted keys are matched by the character they type.
+        let bang = HotKey::Chord(Key::Character('!'), shift);
+        assert!(bang.matches(&Key::Character('!'), &shift));
+        assert!(!HotKey::Key1
+            .with_modifiers(shift)
+            .matches(&Key::Character('!'), &shift));
+    }
+
+    #[test]
+    fn test_hotkey_conflicts() {
+        let cmds = [
+            CommandInfo {
+                path: "File:New".into(),
+                key: Some(HotKey::KeyN),
+                enabled: true,
+                checked: false,
+            },
+            CommandInfo {
+                path: "File:Open".into(),
+                key: Some(HotKey::KeyO),
+                enabled: true,
+                checked: false,
+            },
+            CommandInfo {
+                path: "Edit:Nudge".into(),
+                key: Some(HotKey::Chord(
+                    Key::Character('N'),
+                    KeyboardModifiers::primary(),
+                )),
+                enabled: true,
+                checked: false,
+            },
+        ];
+
+        let conflicts = hotkey_conflicts(&cmds);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(&*conflicts[0].1, "File:New");
+        assert_eq!(&*conflicts[0].2, "Edit:Nudge");
+    }
+
+    #[test]
+    fn test_command_validation() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || (false, String::new()),
+            |s, _| {
+                rectangle()
+                    .command_arg("File:Open Recent", None, move |cx, path: &String| {
+                        cx[s].1 = path.clone()
+                    })
+                    .command("Edit:Undo", None, move |cx| cx[s].1.clear())
+                    .enabled(move |cx| cx[s].0)
+                    .checked(move |cx| cx[s].1.is_empty())
+            },
+        );
+
+        let s = StateHandle::<(bool, String)>::new(cx.view_id(&vec![0]));
+
+        let arg = CommandArg::new(String::from("song.wav"));
+        cx.process(&ui, &Event::Command("File:Open Recent".into(), Some(arg)));
+        assert_eq!(cx[s].1, "song.wav");
+
+        // Wrong argument type is ignored.
+        let arg = CommandArg::new(42);
+        cx.process(&ui, &Event::Command("File:Open Recent".into(), Some(arg)));
+        assert_eq!(cx[s].1, "song.wav");
+
+        let mut cmds = vec![];
+        cx.commands(&ui, &mut cmds);
+        let undo = cmds.iter().find(|c| &*c.path == "Edit:Undo").unwrap();
+        assert!(!undo.enabled);
+        assert!(!undo.checked);
+
+        // Disabled, so nothing happens.
+        cx.process(&ui, &Event::Command("Edit:Undo".into(), None));
+        assert_eq!(cx[s].1, "song.wav");
+
+        cx[s].0 = true;
+        cx.process(&ui, &Event::Command("Edit:Undo".into(), None));
+        assert_eq!(cx[s].1, "");
+
+        let mut cmds = vec![];
+        cx.commands(&ui, &mut cmds);
+        let undo = cmds.iter().find(|c| &*c.path == "Edit:Undo").unwrap();
+        assert!(undo.enabled);
+        assert!(undo.checked);
+    }
+
+    #[test]
+    fn test_disabled_hotkey() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || (false, 0),
+            |s, _| {
+                rectangle()
+                    .key(move |cx, _| cx[s].1 += 1)
+                    .command("File:Save", Some(HotKey::KeyS), move |cx| cx[s].1 += 10)
+                    .enabled(move |cx| cx[s].0)
+            },
+        );
+        let s = StateHandle::<(bool, i32)>::new(cx.view_id(&vec![0]));
+
+        // The key goes to the view while the command is disabled.
+        cx.key_mods = KeyboardModifiers::primary();
+        cx.process(&ui, &Event::Key(Key::Character('s')));
+        assert_eq!(cx[s].1, 1);
+
+        cx[s].0 = true;
+        cx.process(&ui, &Event::Key(Key::Character('s')));
+        assert_eq!(cx[s].1, 11);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::*;
+
+/// Maximum number of commands shown at once.
+const MAX_RESULTS: usize = 12;
+
+#[derive(Default)]
+struct PaletteState {
+    query: String,
+    selected: usize,
+}
+
+/// Scores how well `query` matches `candidate`. The characters of the
+/// query must appear in order, ignoring case. Consecutive matches and
+/// matches at the start of words score higher.
+pub(crate) fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let chars: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev = None;
+
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let i = (pos..chars.len()).find(|&i| chars[i].to_lowercase().eq(q.to_lowercase()))?;
+
+        score += 1;
+        if i > 0 && prev == Some(i - 1) {
+            score += 5;
+        }
+        if i == 0 || !chars[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+
+        prev = Some(i);
+        pos = i + 1;
+    }
+
+    Some(score)
+}
+
+/// Commands matching the query, best matches first.
+fn palette_matches(cmds: &[CommandInfo], query: &str) -> Vec<CommandInfo> {
+    let mut scored: Vec<(i32, &CommandInfo)> = cmds
+        .iter()
+        .filter_map(|cmd| fuzzy_score(query, &cmd.path).map(|score| (score, cmd)))
+        .collect();
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.path.cmp(&b.1.path)));
+    scored.dedup_by(|a, b| a.1.path == b.1.path);
+
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, cmd)| cmd.clone())
+        .collect()
+}
+
+fn run_command(
+    cx: &mut Context,
+    cmd: &CommandInfo,
+    state: StateHandle<PaletteState>,
+    is_open: impl Binding<bool>,
+) {
+    // Disabled commands are listed, but can't be run.
+    if !cmd.enabled {
+        return;
+    }
+    cx.send_command(&cmd.path, None);
+    cx[state] = PaletteState::default();
+    is_open.with_mut(cx, |o| *o = false);
+    cx.blur();
+}
+
+/// Searchable list of all the commands in the view tree.
+///
+/// The palette takes the focus when it opens. Typing filters the list,
+/// the arrow keys select a command, and Enter (or a tap) runs it, unless
+/// it's disabled. Escape closes the palette. The list is refreshed once
+/// the event which opens the palette has been handled.
+///
+/// For example:
+///
+/// ```no_run
+/// # use rui::*;
+/// let primary_shift = KeyboardModifiers {
+///     shift: true,
+///     ..KeyboardModifiers::primary()
+/// };
+/// rui(state(
+///     || false,
+///     move |open, _| {
+///         zstack((
+///             rectangle().command("File:New", Some(HotKey::KeyN), |_| println!("new")),
+///             command_palette(open).padding(Auto),
+///         ))
+///         .command(
+///             "View:Command Palette",
+///             Some(HotKey::KeyP.with_modifiers(primary_shift)),
+///             move |cx| cx[open] = true,
+///         )
+///     },
+/// ));
+/// ```
+pub fn command_palette(is_open: impl Binding<bool>) -> impl View {
+    state(PaletteState::default, move |s, cx| {
+        let matches = palette_matches(&cx.registered_commands, &cx[s].query);
+        let selected = cx[s].selected.min(matches.len().saturating_sub(1));
+        let query = cx[s].query.clone();
+        let theme = cx.theme();
+
+        cond(
+            *is_open.get(cx),
+            popup_focus(move |has_focus| {
+                let matches = matches.clone();
+                let key_matches = matches.clone();
+                vstack((
+                    text(&format!("> {}", query)).padding(Auto),
+                    list((0..matches.len()).collect(), move |i: &usize| {
+                        let cmd = matches[*i].clone();
+                        let hint = cmd.key.map(|k| k.to_string()).unwrap_or_default();
+                        hstack((
+                            text(&cmd.path).color(if cmd.enabled {
+                                theme.text
+                            } else {
+                                theme.secondary_text
+                            }),
+                            spacer(),
+                            text(&hint).color(theme.secondary_text),
+                        ))
+                        .padding(Auto)
+                        .background(rectangle().corner_radius(BUTTON_CORNER_RADIUS).color(
+                            if *i == selected {
+                                theme.button_hover
+                            } else {
+                                CLEAR_COLOR
+                            },
+                        ))
+                        .tap(move |cx| run_command(cx, &cmd, s, is_open))
+                    }),
+                ))
+                .padding(Auto)
+                .background(
+                    rectangle()
+                        .corner_radius(BUTTON_CORNER_RADIUS)
+                        .color(theme.button_background),
+                )
+                .key(move |cx, key| {
+                    if !has_focus {
+                        return;
+                    }
+                    match key {
+                        Key::Escape => {
+                            cx[s] = PaletteState::default();
+                            is_open.with_mut(cx, |o| *o = false);
+                        }
+                        Key::ArrowDown => {
+                            cx[s].selected = (selected + 1).min(key_matches.len().saturating_sub(1))
+                        }
+                        Key::ArrowUp => cx[s].selected = selected.saturating_sub(1),
+                        Key::Enter => {
+                            if let Some(cmd) = key_matches.get(selected) {
+                                run_command(cx, cmd, s, is_open)
+                            }
+                        }
+                        Key::Backspace => {
+                            cx[s].query.pop();
+                            cx[s].selected = 0;
+                        }
+                        _ => (),
+                    }
+                })
+                .text_input(move |cx, input| {
+                    if let (true, TextInputEvent::Insert(text)) = (has_focus, input) {
+                        cx[s].query.push_str(&text);
+                        cx[s].selected = 0;
+                    }
+                })
+            }),
+            EmptyView {},
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("", "File:New").is_some());
+        assert!(fuzzy_score("fn", "File:New").is_some());
+        assert!(fuzzy_score("FN", "File:New").is_some());
+        assert!(fuzzy_score("nf", "File:New").is_none());
+
+        // Word starts beat matches in the middle of a word.
+        assert!(fuzzy_score("new", "File:New") > fuzzy_score("new", "Edit:Renew"));
+
+        // Consecutive characters beat scattered ones.
+        assert!(fuzzy_score("open", "File:Open") > fuzzy_score("open", "Options:Pen"));
+    }
+
+    #[test]
+    fn test_command_palette() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |count, _| {
+                state(
+                    || true,
+                    move |open, _| {
+                        zstack((
+                            rectangle()
+                                .command("File:New", None, move |cx| cx[count] += 1)
+                                .command("File:Open", None, move |cx| cx[count] += 10),
+                            command_palette(open),
+                        ))
+                    },
+                )
+            },
+        );
+
+        let count = StateHandle::<i32>::new(cx.view_id(&vec![0]));
+        let open = StateHandle::<bool>::new(cx.view_id(&vec![0, 0]));
+
+        for c in "opn".chars() {
+            cx.process(&ui, &Event::TextInput(c.to_string()));
+        }
+        cx.process(&ui, &Event::Key(Key::Enter));
+
+        assert_eq!(cx[count], 10);
+        assert!(!cx[open]);
+    }
+
+    #[test]
+    fn test_palette_query() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || true,
+            move |open, _| {
+                zstack((
+                    rectangle()
+                        .command("File:New", None, |_| ())
+                        .command("File:Open", None, |_| ())
+                        .command("File:Save", None, |_| ()),
+                    command_palette(open),
+                ))
+            },
+        );
+
+        let s = StateHandle::<PaletteState>::new(cx.view_id(&vec![0, 0, 1]));
+
+        cx.process(&ui, &Event::TextInput("file".into()));
+        cx.process(&ui, &Event::Key(Key::ArrowDown));
+        assert_eq!(cx[s].selected, 1);
+
+        // A space goes into the query and resets the selection.
+        cx.process(&ui, &Event::Key(Key::Space));
+        cx.process(&ui, &Event::TextInput(" ".into()));
+        assert_eq!(cx[s].query, "file ");
+        assert_eq!(cx[s].selected, 0);
+    }
+
+    #[test]
+    fn test_palette_refresh() {
+        let mut cx = Context::new();
+
+        // Opening the palette also adds a command, which it should list.
+        let ui = state(
+            || (false, 0),
+            |s, _| {
+                state(
+                    || false,
+                    move |open, cx| {
+                        zstack((
+                            cond(
+                                cx[s].0,
+                                rectangle().command("Edit:Find", None, move |cx| cx[s].1 += 1),
+                                EmptyView {},
+                            ),
+                            command_palette(open),
+                        ))
+                        .command(
+                            "View:Palette",
+                            Some(HotKey::KeyP),
+                            move |cx| {
+                                cx[s].0 = true;
+                                cx[open] = true;
+                            },
+                        )
+                    },
+                )
+            },
+        );
+
+        let s = StateHandle::<(bool, i32)>::new(cx.view_id(&vec![0]));
+
+        cx.key_mods = KeyboardModifiers::primary();
+        cx.process(&ui, &Event::Key(Key::Character('p')));
+        cx.key_mods = KeyboardModifiers::default();
+        assert!(cx
+            .registered_commands
+            .iter()
+            .any(|c| &*c.path == "Edit:Find"));
+
+        cx.process(&ui, &Event::TextInput("find".into()));
+        cx.process(&ui, &Event::Key(Key::Enter));
+        assert_eq!(cx[s].1, 1);
+    }
+
+    #[test]
+    fn test_palette_focus() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || true,
+            move |open, _| {
+                zstack((
+                    focus(|_| {
+                        rectangle()
+                            .command("File:Close", None, |_| ())
+                            .enabled(|_| false)
+                    }),
+                    command_palette(open),
+                ))
+            },
+        );
+
+        let open = StateHandle::<bool>::new(cx.view_id(&vec![0]));
+        let s = StateHandle::<PaletteState>::new(cx.view_id(&vec![0, 0, 1]));
+
+        // The palette takes the focus when it opens.
+        cx.process(&ui, &Event::TextInput("close".into()));
+        assert_eq!(cx[s].query, "close");
+
+        // Disabled commands can't be run.
+        cx.process(&ui, &Event::Key(Key::Enter));
+        assert!(cx[open]);
+
+        // Typing meant for another view doesn't reach the palette.
+        let other = cx.view_id(&vec![0, 0, 0]);
+        cx.focus(other);
+        cx.process(&ui, &Event::TextInput("x".into()));
+        cx.process(&ui, &Event::Key(Key::Backspace));
+        assert_eq!(cx[s].query, "close");
+        cx.process(&ui, &Event::Key(Key::Escape));
+        assert!(cx[open]);
+    }
+}
@@ -84,6 +84,24 @@ where
         }
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        if self.cond {
+            path.push(0);
+            self.if_true.focusables(path, xform, cx, ids);
+            path.pop();
+        } else {
+            path.push(1);
+            self.if_false.focusables(path, xform, cx, ids);
+            path.pop();
+        }
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         if self.cond {
             path.push(0);
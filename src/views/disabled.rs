@@ -0,0 +1,244 @@
+use crate::*;
+use std::any::Any;
+
+/// Environment flag set by the `disabled` modifier.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Disabled(pub bool);
+
+/// Environment values replaced while traversing a disabled subtree.
+type SavedEnv = Option<(Option<Disabled>, Option<Theme>)>;
+
+/// Struct for the `disabled` modifier.
+#[derive(Clone)]
+pub struct DisabledView<V> {
+    child: V,
+    disabled: bool,
+}
+
+impl<V> DisabledView<V>
+where
+    V: View,
+{
+    pub fn new(child: V, disabled: bool) -> Self {
+        Self { child, disabled }
+    }
+
+    /// Sets the flag and dims the theme, unless an ancestor already did.
+    fn enter(&self, cx: &mut Context) -> SavedEnv {
+        if !self.disabled || cx.is_disabled() {
+            return None;
+        }
+        let theme = cx.theme().dimmed();
+        Some((cx.set_env(&Disabled(true)), cx.set_env(&theme)))
+    }
+
+    fn exit(cx: &mut Context, saved: SavedEnv) {
+        if let Some((disabled, theme)) = saved {
+            cx.restore_env(disabled);
+            cx.restore_env(theme);
+        }
+    }
+}
+
+impl<V> DynView for DisabledView<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let saved = self.enter(cx);
+        // Input doesn't reach disabled views, but animations and commands do.
+        // So do touch ends and key releases, which finish gestures and key
+        // presses begun before the view was disabled.
+        let input = matches!(
+            event,
+            Event::TouchBegin { .. }
+                | Event::TouchMove { .. }
+                | Event::Key(_)
+                | Event::TextInput(_)
+                | Event::ImePreedit { .. }
+                | Event::ImeCommit(_)
+        );
+        if !(input && cx.is_disabled()) {
+            path.push(0);
+            self.child.process(event, path, cx, actions);
+            path.pop();
+        }
+        Self::exit(cx, saved);
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let saved = self.enter(args.cx);
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        Self::exit(args.cx, saved);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let saved = self.enter(args.cx);
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        Self::exit(args.cx, saved);
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let saved = self.enter(cx);
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+        Self::exit(cx, saved);
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let saved = self.enter(cx);
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        Self::exit(cx, saved);
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        let saved = self.enter(cx);
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+        Self::exit(cx, saved);
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        // Tab skips disabled views.
+        if self.disabled {
+            return;
+        }
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        let saved = self.enter(cx);
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+        Self::exit(cx, saved);
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        let saved = self.enter(cx);
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        Self::exit(cx, saved);
+        node_id
+    }
+}
+
+impl<V> private::Sealed for DisabledView<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_disabled() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |count, _| {
+                vstack((
+                    button("a", move |cx| cx[count] += 1).disabled(true),
+                    button("b", move |cx| cx[count] += 1).disabled(false),
+                ))
+            },
+        );
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut crate::views::paragraph::tests::bounds,
+            },
+        );
+        let count = StateHandle::<i32>::new(cx.view_id(&path));
+
+        for y in [45.0, 15.0] {
+            for event in [
+                Event::TouchBegin {
+                    id: 0,
+                    position: [10.0, y].into(),
+                },
+                Event::TouchEnd {
+                    id: 0,
+                    position: [10.0, y].into(),
+                },
+            ] {
+                cx.process(&ui, &event);
+            }
+        }
+        // Only the enabled button, at the bottom, was pressed.
+        assert_eq!(*count.get(&cx), 1);
+
+        // Tab skips the disabled button, and disabling takes the focus away.
+        cx.process(&ui, &Event::Key(Key::Tab));
+        let b = cx.view_id(&vec![0, 0, 1, 0]);
+        assert_eq!(cx.focused_id, Some(b));
+        let a = cx.view_id(&vec![0, 0, 0, 0]);
+        cx.focus(a);
+        cx.animate(&ui);
+        assert_eq!(cx.focused_id, None);
+    }
+
+    #[test]
+    fn test_disabled_mid_press() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || (0, false),
+            |s, cx| button("a", move |cx| cx[s].0 += 1).disabled(cx[s].1),
+        );
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut crate::views::paragraph::tests::bounds,
+            },
+        );
+        let s = StateHandle::<(i32, bool)>::new(cx.view_id(&path));
+
+        let position = [10.0, 10.0].into();
+        cx.process(&ui, &Event::TouchBegin { id: 0, position });
+        cx[s].1 = true;
+        cx.process(&ui, &Event::TouchEnd { id: 0, position });
+
+        // The touch is released, without pressing the button.
+        assert_eq!(cx[s].0, 0);
+        assert_eq!(cx.touches[0], ViewId::default());
+    }
+}
@@ -26,8 +26,10 @@ pub struct DragFunc<F> {
     pub f: F,
 }
 
-impl<A: 'static, F: Fn(&mut Context, LocalOffset, GestureState, Option<MouseButton>) -> A + Clone> DragFn
-    for DragFunc<F>
+impl<
+        A: 'static,
+        F: Fn(&mut Context, LocalOffset, GestureState, Option<MouseButton>) -> A + Clone,
+    > DragFn for DragFunc<F>
 {
     fn call(
         &self,
@@ -47,8 +49,10 @@ pub struct DragFuncP<F> {
     pub f: F,
 }
 
-impl<A: 'static, F: Fn(&mut Context, LocalPoint, GestureState, Option<MouseButton>) -> A + Clone> DragFn
-    for DragFuncP<F>
+impl<
+        A: 'static,
+        F: Fn(&mut Context, LocalPoint, GestureState, Option<MouseButton>) -> A + Clone,
+    > DragFn for DragFuncP<F>
 {
     fn call(
         &self,
@@ -86,7 +90,9 @@ impl<
         button: Option<MouseButton>,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        actions.push(Box::new((self.f)(self.b.get_mut(cx), delta, state, button)))
+        actions.push(Box::new(
+            self.b.with_mut(cx, |v| (self.f)(v, delta, state, button)),
+        ))
     }
 }
 
@@ -141,6 +147,12 @@ where
                     cx.previous_position[*id] = *position;
                     cx.grab_cursor = self.grab;
 
+                    // Undo the whole drag at once. The group is closed
+                    // even if this view goes away before the touch ends.
+                    let undo = cx.undo_manager();
+                    undo.begin_group("");
+                    cx.drag_undo[*id] = Some(undo);
+
                     self.func.call(
                         cx,
                         *position,
@@ -173,16 +185,30 @@ where
                     cx.touches[*id] = ViewId::default();
                     cx.grab_cursor = false;
 
-                    self.func.call(
-                        cx,
-                        *position,
-                        LocalOffset::zero(),
-                        GestureState::Ended,
-                        cx.mouse_button,
-                        actions,
-                    );
+                    // Views disabled mid-drag release the touch without ending it.
+                    if !cx.is_disabled() {
+                        self.func.call(
+                            cx,
+                            *position,
+                            LocalOffset::zero(),
+                            GestureState::Ended,
+                            cx.mouse_button,
+                            actions,
+                        );
+                    }
+
+                    if let Some(undo) = cx.drag_undo[*id].take() {
+                        cx.flush_undo();
+                        undo.end_group();
+                    }
                 }
             }
+            Event::Anim { .. } => {
+                // Animations inside the gesture still run.
+                path.push(0);
+                self.child.process(event, path, cx, actions);
+                path.pop();
+            }
             _ => (),
         }
     }
@@ -219,6 +245,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(0);
         self.child.gc(path, cx, map);
@@ -301,4 +339,37 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_disabled_mid_drag() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || (vec![], false),
+            |s, cx| {
+                rectangle()
+                    .drag(move |cx, _delta, state, _| cx[s].0.push(state))
+                    .disabled(cx[s].1)
+            },
+        );
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<(Vec<GestureState>, bool)>::new(cx.view_id(&path));
+
+        let position = [50.0, 50.0].into();
+        cx.process(&ui, &Event::TouchBegin { id: 0, position });
+        cx[s].1 = true;
+        cx.process(&ui, &Event::TouchEnd { id: 0, position });
+
+        // The touch is released, without ending the drag.
+        assert_eq!(cx[s].0, vec![GestureState::Began]);
+        assert_eq!(cx.touches[0], ViewId::default());
+    }
 }
@@ -58,6 +58,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        (self.func)(cx.init_env(&S::default), cx).focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         map.push(cx.view_id(path));
         path.push(0);
@@ -169,6 +181,20 @@ where
         old.and_then(|s| cx.set_env(&s));
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let old = cx.set_env(&self.env_val);
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+        old.and_then(|s| cx.set_env(&s));
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         let old = cx.set_env(&self.env_val);
         path.push(0);
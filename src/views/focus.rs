@@ -1,10 +1,41 @@
 use crate::*;
 use std::any::Any;
 
+/// Appearance of the ring drawn around the view with the keyboard focus.
+///
+/// Set it for a subtree with the `env` modifier. A width of zero hides the ring.
+#[derive(Clone, Copy, Debug)]
+pub struct FocusRing {
+    /// The theme's accent color unless set.
+    pub color: Option<Color>,
+    pub width: f32,
+    pub corner_radius: f32,
+
+    /// Space between the ring and the focused view.
+    pub outset: f32,
+}
+
+impl Default for FocusRing {
+    fn default() -> Self {
+        Self {
+            color: None,
+            width: 2.0,
+            corner_radius: BUTTON_CORNER_RADIUS,
+            outset: 2.0,
+        }
+    }
+}
+
 /// Struct for the `focus` modifier.
 #[derive(Clone)]
 pub struct Focus<F> {
     func: F,
+
+    /// Does clicking the view give it the focus?
+    click: bool,
+
+    /// Does the view take the focus when it appears?
+    appear: bool,
 }
 
 impl<V, F> DynView for Focus<F>
@@ -20,31 +51,59 @@ where
         actions: &mut Vec<Box<dyn Any>>,
     ) {
         let vid = cx.view_id(path);
-        match &event {
-            Event::TouchBegin { id: _, position } => {
-                if self.hittest(path, *position, cx).is_some() {
-                    cx.focused_id = Some(vid);
-                    cx.set_dirty();
-                }
-            }
-            Event::Key(Key::Escape) => {
-                if cx.focused_id == Some(vid) {
-                    cx.focused_id = None;
-                    cx.set_dirty();
-                }
+        if cx.is_disabled() && cx.focused_id == Some(vid) {
+            cx.blur();
+        }
+        // Remember having appeared, until the view is removed.
+        if self.appear && !cx.state_map.contains_key(&vid) {
+            cx.set_state(vid, ());
+            cx.focus(vid);
+            cx.focus_visible = false;
+        }
+        if let Event::TouchBegin { id: _, position } = &event {
+            if self.click && self.hittest(path, *position, cx).is_some() {
+                cx.focus(vid);
+                // Only show the focus ring when navigating by keyboard.
+                cx.focus_visible = false;
             }
-            _ => (),
         }
         path.push(0);
         (self.func)(Some(vid) == cx.focused_id).process(event, path, cx, actions);
         path.pop();
+        // The view gets the Escape which releases the focus.
+        if let Event::Key(Key::Escape) = &event {
+            if cx.focused_id == Some(vid) {
+                cx.blur();
+            }
+        }
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
         let id = args.cx.view_id(path);
+        let has_focus = Some(id) == args.cx.focused_id;
         path.push(0);
-        (self.func)(Some(id) == args.cx.focused_id).draw(path, args);
+        (self.func)(has_focus).draw(path, args);
         path.pop();
+
+        if has_focus && args.cx.focus_visible {
+            let ring = args.cx.init_env(&FocusRing::default);
+            if ring.width > 0.0 {
+                let rect = args
+                    .cx
+                    .get_layout(path)
+                    .rect
+                    .inflate(ring.outset, ring.outset);
+                let color = ring.color.unwrap_or(args.cx.theme().accent);
+                let paint = args.vger.color_paint(args.cx.faded(color));
+                args.vger.stroke_rect(
+                    rect.min(),
+                    rect.max(),
+                    ring.corner_radius,
+                    ring.width,
+                    paint,
+                );
+            }
+        }
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
@@ -52,6 +111,15 @@ where
         path.push(0);
         let sz = (self.func)(Some(id) == args.cx.focused_id).layout(path, args);
         path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+
         sz
     }
 
@@ -77,8 +145,34 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let id = cx.view_id(path);
+        let rect = xform.outer_transformed_rect(&cx.get_layout(path).rect);
+        let index = ids.len();
+        ids.push(FocusInfo {
+            id,
+            order: 0,
+            rect,
+            group: None,
+            accepts_tab: false,
+            text_input: false,
+        });
+        let outer = std::mem::replace(&mut cx.found_text_input, false);
+        path.push(0);
+        (self.func)(Some(id) == cx.focused_id).focusables(path, xform, cx, ids);
+        path.pop();
+        ids[index].text_input = std::mem::replace(&mut cx.found_text_input, outer);
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         let id = cx.view_id(path);
+        map.push(id);
         path.push(0);
         (self.func)(Some(id) == cx.focused_id).gc(path, cx, map);
         path.pop();
@@ -103,5 +197,314 @@ impl<F> private::Sealed for Focus<F> {}
 /// Calls calls a function with true if the view subtree returned
 /// by the function has the keyboard focus.
 pub fn focus<V: View, F: Fn(bool) -> V + Clone + 'static>(f: F) -> impl View {
-    Focus { func: f }
+    Focus {
+        func: f,
+        click: true,
+        appear: false,
+    }
+}
+
+/// Like `focus`, but the view only takes the focus from the keyboard.
+/// Clicking a control then leaves the focus with the text field being edited.
+pub(crate) fn keyboard_focus<V: View, F: Fn(bool) -> V + Clone + 'static>(f: F) -> impl View {
+    Focus {
+        func: f,
+        click: false,
+        appear: false,
+    }
+}
+
+/// Like `focus`, but the view takes the focus when it appears, as a
+/// popup does.
+pub(crate) fn popup_focus<V: View, F: Fn(bool) -> V + Clone + 'static>(f: F) -> impl View {
+    Focus {
+        func: f,
+        click: true,
+        appear: true,
+    }
+}
+
+/// Struct for the `focus_order`, `focusable`, `focus_group` and `accepts_tab` modifiers.
+#[derive(Clone)]
+pub struct FocusTraversal<V> {
+    child: V,
+    order: Option<i32>,
+    focusable: bool,
+    group: bool,
+    accepts_tab: bool,
+}
+
+impl<V> FocusTraversal<V>
+where
+    V: View,
+{
+    pub fn new(
+        child: V,
+        order: Option<i32>,
+        focusable: bool,
+        group: bool,
+        accepts_tab: bool,
+    ) -> Self {
+        Self {
+            child,
+            order,
+            focusable,
+            group,
+            accepts_tab,
+        }
+    }
+}
+
+impl<V> DynView for FocusTraversal<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        if !self.focusable {
+            return;
+        }
+        let start = ids.len();
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+        let group = cx.view_id(path);
+        for info in &mut ids[start..] {
+            if let Some(order) = self.order {
+                info.order = order;
+            }
+            // Nested groups take precedence.
+            if self.group && info.group.is_none() {
+                info.group = Some(group);
+            }
+            info.accepts_tab |= self.accepts_tab;
+        }
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for FocusTraversal<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_focus_traversal() {
+        let mut cx = Context::new();
+
+        let ui = hstack((
+            focus(|_| rectangle()),
+            focus(|_| rectangle()).focusable(false),
+            focus(|_| rectangle()).focus_order(-1),
+            focus(|_| rectangle()),
+        ));
+
+        let a = cx.view_id(&vec![0, 0]);
+        let c = cx.view_id(&vec![0, 2, 0]);
+        let d = cx.view_id(&vec![0, 3]);
+
+        let mut focused = vec![];
+        for _ in 0..4 {
+            cx.process(&ui, &Event::Key(Key::Tab));
+            focused.push(cx.focused_id.unwrap());
+        }
+        assert_eq!(focused, vec![c, a, d, c]);
+
+        cx.key_mods.shift = true;
+        cx.process(&ui, &Event::Key(Key::Tab));
+        assert_eq!(cx.focused_id, Some(d));
+
+        cx.blur();
+        cx.process(&ui, &Event::Key(Key::Tab));
+        assert_eq!(cx.focused_id, Some(d));
+    }
+
+    #[test]
+    fn test_accepts_tab() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |tabs, _| {
+                hstack((
+                    focus(move |has_focus| {
+                        rectangle().key(move |cx, k| {
+                            if has_focus && k == Key::Tab {
+                                cx[tabs] += 1
+                            }
+                        })
+                    })
+                    .accepts_tab(),
+                    focus(|_| rectangle()),
+                ))
+            },
+        );
+
+        let tabs = StateHandle::<i32>::new(cx.view_id(&vec![0]));
+        let a = cx.view_id(&vec![0, 0, 0, 0]);
+        let b = cx.view_id(&vec![0, 0, 1]);
+
+        cx.focus(b);
+        cx.process(&ui, &Event::Key(Key::Tab));
+        assert_eq!(cx.focused_id, Some(a));
+
+        // The focused view keeps the focus and gets the key.
+        cx.process(&ui, &Event::Key(Key::Tab));
+        assert_eq!(cx.focused_id, Some(a));
+        assert_eq!(cx[tabs], 1);
+
+        // Escape gives up the focus, so Tab moves it again.
+        cx.process(&ui, &Event::Key(Key::Escape));
+        cx.process(&ui, &Event::Key(Key::Tab));
+        assert_eq!(cx.focused_id, Some(a));
+        assert_eq!(cx[tabs], 1);
+    }
+
+    #[test]
+    fn test_focus_direction() {
+        let mut cx = Context::new();
+
+        let ui = vstack((
+            hstack((focus(|_| rectangle()), focus(|_| rectangle()))),
+            hstack((focus(|_| rectangle()), focus(|_| rectangle()))),
+        ))
+        .focus_group();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+
+        let a = cx.view_id(&vec![0, 0, 0, 0]);
+        let b = cx.view_id(&vec![0, 0, 0, 1]);
+        let c = cx.view_id(&vec![0, 0, 1, 0]);
+        let d = cx.view_id(&vec![0, 0, 1, 1]);
+
+        // Arrows don't take the focus when nothing is focused.
+        cx.process(&ui, &Event::Key(Key::ArrowRight));
+        assert_eq!(cx.focused_id, None);
+
+        cx.focus(a);
+        let moves = [
+            (Key::ArrowRight, b),
+            (Key::ArrowDown, d),
+            (Key::ArrowLeft, c),
+            (Key::ArrowUp, a),
+            (Key::ArrowUp, a),
+        ];
+        for (key, expected) in moves {
+            cx.process(&ui, &Event::Key(key));
+            assert_eq!(cx.focused_id, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_focus_direction_editors() {
+        let mut cx = Context::new();
+
+        // Views editing text or taking Tab need the arrow keys.
+        let ui = hstack((
+            focus(|_| rectangle().text_input(|_, _| ())),
+            focus(|_| rectangle()).accepts_tab(),
+            focus(|_| rectangle()),
+        ))
+        .focus_group();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [150.0, 50.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+
+        let a = cx.view_id(&vec![0, 0, 0]);
+        let b = cx.view_id(&vec![0, 0, 1, 0]);
+        let c = cx.view_id(&vec![0, 0, 2]);
+
+        cx.focus(a);
+        cx.process(&ui, &Event::Key(Key::ArrowRight));
+        assert_eq!(cx.focused_id, Some(a));
+
+        cx.focus(c);
+        cx.process(&ui, &Event::Key(Key::ArrowLeft));
+        assert_eq!(cx.focused_id, Some(b));
+        cx.process(&ui, &Event::Key(Key::ArrowLeft));
+        assert_eq!(cx.focused_id, Some(b));
+    }
 }
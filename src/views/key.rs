@@ -47,7 +47,7 @@ where
     fn process(
         &self,
         event: &Event,
-        _path: &mut IdPath,
+        path: &mut IdPath,
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
@@ -63,6 +63,9 @@ where
                 }
             }
         }
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
@@ -97,6 +100,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(0);
         self.child.gc(path, cx, map);
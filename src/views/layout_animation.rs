@@ -0,0 +1,255 @@
+use crate::*;
+use std::any::Any;
+
+/// Where a view is shown while its layout animates.
+#[derive(Clone, Copy, Default)]
+struct LayoutMotion {
+    /// Offset within the parent, which is set by the parent after layout.
+    offset: Option<Animated<LocalOffset>>,
+    size: Option<Animated<LocalSize>>,
+}
+
+/// Moves `animated` to `target`, or starts it there the first time.
+fn retarget<T: Animatable>(
+    animated: &mut Option<Animated<T>>,
+    target: T,
+    animation: Animation,
+) -> T {
+    match animated {
+        Some(animated) => {
+            animated.set(target, Some(animation));
+            animated.value()
+        }
+        None => {
+            *animated = Some(Animated::new(target));
+            target
+        }
+    }
+}
+
+/// Struct for the `animate_layout` modifier.
+#[derive(Clone)]
+pub struct LayoutAnimation<V> {
+    child: V,
+    animation: Animation,
+}
+
+impl<V> LayoutAnimation<V>
+where
+    V: View,
+{
+    pub fn new(child: V, animation: Animation) -> Self {
+        Self { child, animation }
+    }
+
+    fn motion<'a>(&self, path: &IdPath, cx: &'a mut Context) -> &'a mut LayoutMotion {
+        let id = cx.view_id(path);
+        cx.view_state(id, LayoutMotion::default)
+    }
+
+    /// Retargets the offset to the one the parent laid the view out at,
+    /// and returns how far from it the view is shown.
+    fn update_offset(&self, path: &IdPath, cx: &mut Context) -> LocalOffset {
+        let target = cx.get_layout(path).offset;
+        let motion = self.motion(path, cx);
+        retarget(&mut motion.offset, target, self.animation) - target
+    }
+
+    /// How far from its laid out offset the view is shown.
+    fn offset(&self, path: &IdPath, cx: &mut Context) -> LocalOffset {
+        let target = cx.get_layout(path).offset;
+        match self.motion(path, cx).offset {
+            Some(offset) => offset.value() - target,
+            None => LocalOffset::zero(),
+        }
+    }
+}
+
+impl<V> DynView for LayoutAnimation<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim { dt, .. } = event {
+            let motion = self.motion(path, cx);
+            let mut running = false;
+            let mut moved = false;
+            if let Some(offset) = &mut motion.offset {
+                moved |= offset.is_running();
+                running |= offset.step(*dt);
+            }
+            if let Some(size) = &mut motion.size {
+                moved |= size.is_running();
+                running |= size.step(*dt);
+            }
+            if moved {
+                // The size changes layout, so mark the state as changed.
+                let id = cx.view_id(path);
+                cx.state_map.get_mut(&id).unwrap().dirty = true;
+                cx.set_dirty();
+            }
+            if running {
+                cx.request_frame();
+            }
+        }
+
+        let offset = self.offset(path, cx);
+        path.push(0);
+        self.child
+            .process(&event.offset(-offset), path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let offset = self.update_offset(path, args.cx);
+        args.vger.save();
+        args.vger.translate(offset);
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        let motion = self.motion(path, args.cx);
+        retarget(&mut motion.size, sz, self.animation)
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let offset = self.update_offset(path, cx);
+        path.push(0);
+        self.child.dirty(path, xform.pre_translate(offset), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let offset = self.offset(path, cx);
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt - offset, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let offset = self.offset(path, cx);
+        path.push(0);
+        self.child
+            .focusables(path, xform.pre_translate(offset), cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for LayoutAnimation<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_animate_layout() {
+        let mut cx = Context::new();
+        let clock = VirtualClock::new();
+        cx.set_clock(clock.clone());
+
+        let ui = state(
+            || vec![1, 2],
+            |ids, cx| {
+                list(cx[ids].clone(), |_| {
+                    rectangle()
+                        .size([10.0, 10.0])
+                        .animate_layout(Animation::ease_in_out(0.5))
+                })
+            },
+        );
+
+        let mut path = vec![0];
+        let mut frame = |cx: &mut Context| {
+            clock.advance(0.1);
+            cx.animate(&ui);
+            ui.layout(
+                &mut path,
+                &mut LayoutArgs {
+                    sz: [100.0, 100.0].into(),
+                    cx,
+                    text_bounds: &mut |_, _, _| LocalRect::zero(),
+                },
+            );
+            ui.dirty(&mut path, LocalToWorld::identity(), cx);
+            cx.clear_dirty();
+        };
+
+        // Where the view with id 1 is shown.
+        let shown = |cx: &mut Context| {
+            let path = vec![0, 0, hh(&1)];
+            let id = cx.view_id(&path);
+            let motion = cx.state_map[&id]
+                .state
+                .downcast_ref::<LayoutMotion>()
+                .unwrap();
+            (motion.offset.unwrap().value(), cx.get_layout(&path).offset)
+        };
+
+        frame(&mut cx);
+        let (start, target) = shown(&mut cx);
+        assert_eq!(start, target);
+
+        // Moving it to the end animates it there.
+        let ids = StateHandle::<Vec<i32>>::new(cx.view_id(&vec![0]));
+        cx[ids] = vec![2, 1];
+        frame(&mut cx);
+        let (_, end) = shown(&mut cx);
+        assert_ne!(end, start);
+        let mut ys = vec![];
+        for _ in 0..8 {
+            frame(&mut cx);
+            ys.push(shown(&mut cx).0.y);
+        }
+        assert!(ys.iter().any(|y| *y != start.y && *y != end.y));
+        assert!(ys
+            .windows(2)
+            .all(|w| (w[1] - end.y).abs() <= (w[0] - end.y).abs()));
+        assert_eq!(ys[7], end.y);
+    }
+}
@@ -186,6 +186,21 @@ where
         }
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        for child in &self.ids {
+            path.push(hh(child));
+            let offset = cx.get_layout(path).offset;
+            ((self.func)(child)).focusables(path, xform.pre_translate(offset), cx, ids);
+            path.pop();
+        }
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         map.push(cx.view_id(path));
         for child in &self.ids {
@@ -240,7 +255,10 @@ impl<ID, F> private::Sealed for List<ID, F> {}
 ///     ))
 /// }));
 /// ```
-pub fn list<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(ids: Vec<ID>, f: F) -> List<ID, F> {
+pub fn list<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(
+    ids: Vec<ID>,
+    f: F,
+) -> List<ID, F> {
     List {
         orientation: ListOrientation::Vertical,
         ids,
@@ -248,7 +266,10 @@ pub fn list<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(ids: V
     }
 }
 
-pub fn hlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(ids: Vec<ID>, f: F) -> List<ID, F> {
+pub fn hlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(
+    ids: Vec<ID>,
+    f: F,
+) -> List<ID, F> {
     List {
         orientation: ListOrientation::Horizontal,
         ids,
@@ -256,7 +277,10 @@ pub fn hlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(ids:
     }
 }
 
-pub fn zlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(ids: Vec<ID>, f: F) -> List<ID, F> {
+pub fn zlist<ID: Hash + Clone, V: View, F: Fn(&ID) -> V + Clone + 'static>(
+    ids: Vec<ID>,
+    f: F,
+) -> List<ID, F> {
     List {
         orientation: ListOrientation::Z,
         ids,
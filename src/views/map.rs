@@ -78,6 +78,20 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let id = cx.view_id(path);
+        cx.set_state(id, self.value.clone());
+        path.push(0);
+        (self.func)(StateHandle::new(id), cx).focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         let id = cx.view_id(path);
         cx.set_state(id, self.value.clone());
@@ -0,0 +1,268 @@
+use crate::*;
+use std::any::Any;
+use std::hash::Hash;
+
+/// Where a view with `matched_geometry` moves from after taking over
+/// from a removed view.
+#[derive(Clone, Copy)]
+struct MatchedState {
+    /// Bounds of the removed view, in the local space of this one.
+    from: LocalRect,
+
+    /// 0 when shown at `from`, 1 when shown at its own bounds.
+    progress: Animated<f32>,
+}
+
+impl Default for MatchedState {
+    fn default() -> Self {
+        Self {
+            from: LocalRect::zero(),
+            progress: Animated::new(1.0),
+        }
+    }
+}
+
+/// Struct for the `matched_geometry` modifier.
+#[derive(Clone)]
+pub struct MatchedGeometry<V> {
+    child: V,
+    key: u64,
+}
+
+impl<V> MatchedGeometry<V>
+where
+    V: View,
+{
+    pub fn new(child: V, id: impl Hash, namespace: impl Hash) -> Self {
+        Self {
+            child,
+            key: hh(&(hh(&namespace), hh(&id))),
+        }
+    }
+
+    /// Records the bounds of the view, drawn with `xform`. If it took the
+    /// place of a removed view with the same key, starts moving from there.
+    fn track(&self, path: &IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let id = cx.view_id(path);
+        let bounds = LocalRect::new(LocalPoint::zero(), cx.get_layout(path).rect.size);
+        if let Some(&(other, from)) = cx.matched_geometry.get(&self.key) {
+            if other != id {
+                if cx.state_map.contains_key(&other) {
+                    // Both views are in the tree, and the other one was first.
+                    return;
+                }
+                if let Some(inverse) = xform.inverse() {
+                    let animation = cx
+                        .last_transaction()
+                        .unwrap_or(Animation::spring(170.0, 26.0));
+                    let state = self.state(path, cx);
+                    state.from = inverse.outer_transformed_rect(&from);
+                    state.progress = Animated::new(0.0);
+                    state.progress.set(1.0, Some(animation));
+                    cx.request_frame();
+                }
+            }
+        }
+        cx.matched_geometry
+            .insert(self.key, (id, xform.outer_transformed_rect(&bounds)));
+    }
+
+    fn state<'a>(&self, path: &IdPath, cx: &'a mut Context) -> &'a mut MatchedState {
+        let id = cx.view_id(path);
+        cx.view_state(id, MatchedState::default)
+    }
+}
+
+impl<V> DynView for MatchedGeometry<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim { dt, .. } = event {
+            let progress = &mut self.state(path, cx).progress;
+            if progress.is_running() {
+                if progress.step(*dt) {
+                    cx.request_frame();
+                }
+                cx.set_dirty();
+            }
+        }
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        self.track(path, args.vger.current_transform(), args.cx);
+        let size = args.cx.get_layout(path).rect.size;
+        let state = *self.state(path, args.cx);
+        let p = state.progress.value();
+
+        args.vger.save();
+        if p < 1.0 && size.width > 0.0 && size.height > 0.0 {
+            let origin = state.from.origin.to_vector() * (1.0 - p);
+            let shown = state.from.size.lerp(size, p);
+            args.vger.translate(origin);
+            args.vger
+                .scale([shown.width / size.width, shown.height / size.height]);
+        }
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        // Keep state for every matched view, so `dirty` can tell which are in the tree.
+        self.state(path, cx);
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for MatchedGeometry<V> {}
+
+impl Context {
+    /// Forgets the bounds of matched views which were removed without
+    /// another view taking their place.
+    pub(crate) fn prune_matched_geometry(&mut self) {
+        let state_map = &self.state_map;
+        self.matched_geometry
+            .retain(|_, (id, _)| state_map.contains_key(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_matched_geometry() {
+        let mut cx = Context::new();
+        let small = rectangle().size([20.0, 20.0]).matched_geometry(1, "clip");
+        let large = rectangle().size([80.0, 80.0]).matched_geometry(1, "clip");
+        let (small_path, large_path) = (vec![0], vec![1]);
+
+        let show = |view: &dyn DynView, path: &IdPath, cx: &mut Context| {
+            let mut path = path.clone();
+            view.gc(&mut path, cx, &mut vec![]);
+            view.layout(
+                &mut path,
+                &mut LayoutArgs {
+                    sz: [100.0, 100.0].into(),
+                    cx,
+                    text_bounds: &mut |_, _, _| LocalRect::zero(),
+                },
+            );
+        };
+        let state = |path: &IdPath, cx: &mut Context| {
+            let id = cx.view_id(path);
+            *cx.state_map[&id]
+                .state
+                .downcast_ref::<MatchedState>()
+                .unwrap()
+        };
+
+        show(&small, &small_path, &mut cx);
+        small.track(&small_path, LocalToWorld::translation(50.0, 50.0), &mut cx);
+
+        // While both are shown, the first one keeps its place.
+        show(&large, &large_path, &mut cx);
+        large.track(&large_path, LocalToWorld::translation(10.0, 10.0), &mut cx);
+        assert_eq!(state(&large_path, &mut cx).progress.value(), 1.0);
+
+        // Once the small one is removed, the large one moves from where it was.
+        let small_id = cx.view_id(&small_path);
+        cx.state_map.remove(&small_id);
+        large.track(&large_path, LocalToWorld::translation(10.0, 10.0), &mut cx);
+        let moving = state(&large_path, &mut cx);
+        assert_eq!(moving.progress.value(), 0.0);
+        assert_eq!(
+            moving.from,
+            LocalRect::new([40.0, 40.0].into(), [20.0, 20.0].into())
+        );
+        assert!(cx.wants_frame());
+
+        let mut frames = 0;
+        while state(&large_path, &mut cx).progress.value() < 1.0 {
+            let event = Event::Anim {
+                dt: 1.0 / 60.0,
+                time: 0.0,
+            };
+            large.process(&event, &mut large_path.clone(), &mut cx, &mut vec![]);
+            frames += 1;
+        }
+        assert!(frames > 10 && frames < 120);
+
+        // Once settled, later frames don't move it again.
+        large.track(&large_path, LocalToWorld::translation(10.0, 10.0), &mut cx);
+        assert_eq!(state(&large_path, &mut cx).progress.value(), 1.0);
+    }
+}
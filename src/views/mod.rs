@@ -4,6 +4,8 @@ mod anyview;
 pub use anyview::*;
 mod background;
 pub use background::*;
+mod border;
+pub use border::*;
 mod button;
 pub use button::*;
 mod canvas;
@@ -12,8 +14,12 @@ mod clip;
 pub use clip::*;
 mod command;
 pub use command::*;
+mod command_palette;
+pub use command_palette::*;
 mod cond;
 pub use cond::*;
+mod disabled;
+pub use disabled::*;
 mod drag;
 pub use drag::*;
 mod emptyview;
@@ -34,22 +40,38 @@ mod key;
 pub use key::*;
 mod knob;
 pub use knob::*;
+mod layout_animation;
+pub use layout_animation::*;
 mod list;
 pub use list::*;
 mod map;
 pub use map::*;
+mod matched_geometry;
+pub use matched_geometry::*;
 mod modview;
 pub use modview::*;
+mod number_field;
+pub use number_field::*;
 mod offset;
 pub use offset::*;
+mod opacity;
+pub use opacity::*;
 mod padding;
 pub use padding::*;
+mod path;
+pub use path::*;
+pub(crate) mod paragraph;
+pub use paragraph::*;
 mod redux;
 pub use redux::*;
+mod rich_text;
+pub use rich_text::*;
 mod role;
 pub use role::*;
 mod shapes;
 pub use shapes::*;
+mod shadow;
+pub use shadow::*;
 mod size;
 pub use size::*;
 mod slider;
@@ -61,15 +83,26 @@ pub use stack::*;
 mod stack_layout;
 mod state;
 pub use state::*;
+mod style;
+pub use style::*;
 mod tap;
 pub use tap::*;
 mod touch;
 pub use touch::*;
 mod text_editor;
 pub use text_editor::*;
+
+mod text_field;
+pub use text_field::*;
 mod text;
 pub use text::*;
+mod text_input;
+pub use text_input::*;
 mod toggle;
 pub use toggle::*;
+mod transform;
+pub use transform::*;
+mod transition;
+pub use transition::*;
 mod window;
 pub use window::*;
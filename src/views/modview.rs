@@ -64,6 +64,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        (self.func)(self.value.clone(), cx).focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         map.push(cx.view_id(path));
         path.push(0);
@@ -85,7 +97,11 @@ where
 }
 
 /// Passes a value to a function. Value can be updated by modifiers.
-pub fn modview<S: Clone + Default + 'static, V: View, F: Fn(S, &mut Context) -> V + Clone + 'static>(
+pub fn modview<
+    S: Clone + Default + 'static,
+    V: View,
+    F: Fn(S, &mut Context) -> V + Clone + 'static,
+>(
     f: F,
 ) -> ModView<S, F> {
     ModView {
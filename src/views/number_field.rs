@@ -0,0 +1,358 @@
+use crate::*;
+use std::sync::Arc;
+
+const NUMBER_FIELD_FONT_SIZE: u32 = 18;
+const NUMBER_FIELD_CORNER_RADIUS: f32 = 4.0;
+
+/// How much slower the value changes while Shift is held.
+const FINE_ADJUSTMENT: f32 = 0.1;
+
+/// Unit of the value in a `number_field`, used to format and parse it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Unit {
+    /// A plain number.
+    #[default]
+    None,
+
+    /// Frequency. Shown in kHz from 1000 Hz.
+    Hertz,
+
+    /// Gain.
+    Decibels,
+
+    /// Time. Shown in seconds from 1000 ms.
+    Milliseconds,
+}
+
+impl Unit {
+    /// Formats a value for display.
+    pub fn format(self, value: f32) -> String {
+        match self {
+            Unit::None => format!("{:.2}", value),
+            Unit::Hertz if value.abs() >= 1000.0 => format!("{:.2} kHz", value / 1000.0),
+            Unit::Hertz => format!("{:.1} Hz", value),
+            Unit::Decibels => format!("{:.1} dB", value),
+            Unit::Milliseconds if value.abs() >= 1000.0 => format!("{:.2} s", value / 1000.0),
+            Unit::Milliseconds => format!("{:.1} ms", value),
+        }
+    }
+
+    /// Parses typed text, like "1.2k", "1.2 kHz" or "440hz" for `Unit::Hertz`.
+    ///
+    /// The unit suffix is optional and case insensitive. Returns `None` if the text
+    /// isn't a number or has a suffix for a different unit.
+    pub fn parse(self, text: &str) -> Option<f32> {
+        let text = text.trim();
+        let split = text
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+            .unwrap_or(text.len());
+        let (number, suffix) = text.split_at(split);
+        let value: f32 = number.parse().ok()?;
+
+        let scale = match (self, suffix.trim().to_lowercase().as_str()) {
+            (_, "") => 1.0,
+            (Unit::Hertz, "hz") | (Unit::Decibels, "db") | (Unit::Milliseconds, "ms") => 1.0,
+            (Unit::Hertz, "k") | (Unit::Hertz, "khz") => 1000.0,
+            (Unit::Milliseconds, "s") => 1000.0,
+            _ => return None,
+        };
+        Some(value * scale)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct NumberFieldOptions {
+    unit: Unit,
+    min: f32,
+    max: f32,
+    step: f32,
+}
+
+impl Default for NumberFieldOptions {
+    fn default() -> Self {
+        Self {
+            unit: Unit::None,
+            min: f32::MIN,
+            max: f32::MAX,
+            step: 1.0,
+        }
+    }
+}
+
+impl NumberFieldOptions {
+    fn clamp(&self, value: f32) -> f32 {
+        value.clamp(self.min, self.max)
+    }
+
+    /// Step for arrow keys and for each point dragged, taking Shift into account.
+    fn step(&self, cx: &Context) -> f32 {
+        if cx.key_mods.shift {
+            self.step * FINE_ADJUSTMENT
+        } else {
+            self.step
+        }
+    }
+}
+
+pub trait NumberFieldMods: View + Sized {
+    /// Unit used to show and parse the value.
+    fn unit(self, unit: Unit) -> Self;
+
+    /// Range the value is clamped to.
+    fn range(self, range: std::ops::RangeInclusive<f32>) -> Self;
+
+    /// Change in value for each arrow key press, and for each point dragged.
+    fn step(self, step: f32) -> Self;
+}
+
+impl<F> NumberFieldMods for ModView<NumberFieldOptions, F>
+where
+    ModView<NumberFieldOptions, F>: View,
+{
+    fn unit(mut self, unit: Unit) -> Self {
+        self.value.unit = unit;
+        self
+    }
+
+    fn range(mut self, range: std::ops::RangeInclusive<f32>) -> Self {
+        self.value.min = *range.start();
+        self.value.max = *range.end();
+        self
+    }
+
+    fn step(mut self, step: f32) -> Self {
+        self.value.step = step;
+        self
+    }
+}
+
+#[derive(Default)]
+struct NumberFieldState {
+    /// Is the value being typed?
+    editing: bool,
+
+    /// Text being typed.
+    text: String,
+
+    /// Did the current drag change the value? If not, it was a click.
+    scrubbed: bool,
+
+    /// Was the typed text rejected?
+    invalid: bool,
+}
+
+make_lens!(NumberTextLens, NumberFieldState, String, text);
+
+/// Starts typing a value, replacing the text in the editor.
+fn begin_editing(
+    cx: &mut Context,
+    s: StateHandle<NumberFieldState>,
+    editor: StateHandle<TextEditorState>,
+    text: String,
+    select: bool,
+) {
+    let len = text.len();
+    cx[s].editing = true;
+    cx[s].invalid = false;
+    cx[s].text = text;
+    cx[editor] = TextEditorState::new();
+    if select {
+        cx[editor].select(0..len);
+    } else {
+        cx[editor].select(len..len);
+    }
+}
+
+/// Applies the typed text. Returns false if it couldn't be parsed.
+fn commit(
+    cx: &mut Context,
+    s: StateHandle<NumberFieldState>,
+    value: impl Binding<f32>,
+    opts: NumberFieldOptions,
+) -> bool {
+    match opts.unit.parse(&cx[s].text) {
+        Some(v) => {
+            value.with_mut(cx, |x| *x = opts.clamp(v));
+            cx[s].editing = false;
+            true
+        }
+        None => {
+            cx[s].invalid = true;
+            false
+        }
+    }
+}
+
+/// Field for entering a number, for parameters like frequencies and gains.
+///
+/// Drag horizontally to scrub the value, holding Shift for finer control, or
+/// use the arrow keys to step it. Click or start typing to enter a value. Enter
+/// applies the typed value and Escape cancels it. Like `text_field`, it fills the
+/// space it is offered.
+pub fn number_field(value: impl Binding<f32>) -> impl NumberFieldMods {
+    modview(move |opts: NumberFieldOptions, _| {
+        focus(move |has_focus| {
+            state(NumberFieldState::default, move |s, _| {
+                state(TextEditorState::new, move |editor, cx| {
+                    let text_opts = TextFieldOptions {
+                        on_submit: Some(Arc::new(move |cx| {
+                            commit(cx, s, value, opts);
+                        })),
+                        error: cx[s].invalid,
+                        ..Default::default()
+                    };
+                    let formatted = opts.unit.format(*value.get(cx));
+
+                    cond(
+                        has_focus && cx[s].editing,
+                        text_field_body(bind(s, NumberTextLens {}), text_opts, true, editor, cx),
+                        canvas(move |cx, rect, vger| {
+                            let theme = cx.theme();
+                            let background = vger.color_paint(theme.control_background);
+                            vger.fill_rect(rect, NUMBER_FIELD_CORNER_RADIUS, background);
+
+                            // Monospace keeps the digits still while scrubbing.
+                            let font = Font::monospace();
+                            let size = NUMBER_FIELD_FONT_SIZE;
+                            let width =
+                                text_advance(&formatted, size, &font, 0.0, &mut |t, s, w| {
+                                    vger.text_bounds(t, s, w)
+                                });
+                            let origin = [
+                                rect.min_x() + (rect.width() - width) / 2.0,
+                                rect.min_y() + (rect.height() - size as f32 * 0.7) / 2.0,
+                            ];
+                            draw_text(
+                                vger,
+                                &formatted,
+                                size,
+                                cx.faded(theme.text),
+                                &font,
+                                origin.into(),
+                                0.0,
+                            );
+                        })
+                        .geom(move |cx, _, _| {
+                            // Apply typed text when the focus moves elsewhere.
+                            if cx[s].editing && !commit(cx, s, value, opts) {
+                                cx[s].editing = false;
+                            }
+                        })
+                        .drag(move |cx, delta, gesture, _| match gesture {
+                            GestureState::Began => cx[s].scrubbed = false,
+                            GestureState::Changed => {
+                                if delta.x != 0.0 {
+                                    cx[s].scrubbed = true;
+                                    let step = opts.step(cx);
+                                    value.with_mut(cx, |v| *v = opts.clamp(*v + delta.x * step));
+                                }
+                            }
+                            GestureState::Ended => {
+                                if !cx[s].scrubbed {
+                                    let text = opts.unit.format(*value.get(cx));
+                                    begin_editing(cx, s, editor, text, true);
+                                }
+                            }
+                        })
+                        .grab_cursor()
+                        .key(move |cx, k| {
+                            if k == Key::Escape {
+                                // Focus has already moved away, so cancel typing.
+                                cx[s].editing = false;
+                            }
+                            if !has_focus {
+                                return;
+                            }
+                            let step = opts.step(cx);
+                            let delta = match k {
+                                Key::ArrowUp | Key::ArrowRight => step,
+                                Key::ArrowDown | Key::ArrowLeft => -step,
+                                Key::Enter => {
+                                    let text = opts.unit.format(*value.get(cx));
+                                    begin_editing(cx, s, editor, text, true);
+                                    return;
+                                }
+                                _ => return,
+                            };
+                            value.with_mut(cx, |v| *v = opts.clamp(*v + delta));
+                        })
+                        .text_input(move |cx, input| {
+                            if let (true, TextInputEvent::Insert(text)) = (has_focus, input) {
+                                begin_editing(cx, s, editor, text, false);
+                            }
+                        }),
+                    )
+                })
+            })
+        })
+        .role(accesskit::Role::SpinButton)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_units() {
+        assert_eq!(Unit::Hertz.format(440.0), "440.0 Hz");
+        assert_eq!(Unit::Hertz.format(1500.0), "1.50 kHz");
+        assert_eq!(Unit::Decibels.format(-6.0), "-6.0 dB");
+        assert_eq!(Unit::Milliseconds.format(2500.0), "2.50 s");
+
+        assert_eq!(Unit::Hertz.parse("440"), Some(440.0));
+        assert_eq!(Unit::Hertz.parse(" 1.2 kHz"), Some(1200.0));
+        assert_eq!(Unit::Hertz.parse("2k"), Some(2000.0));
+        assert_eq!(Unit::Decibels.parse("-3dB"), Some(-3.0));
+        assert_eq!(Unit::Milliseconds.parse("0.5 s"), Some(500.0));
+        assert_eq!(Unit::Hertz.parse("3 dB"), None);
+        assert_eq!(Unit::None.parse("abc"), None);
+
+        // Formatted values parse back.
+        for unit in [Unit::Hertz, Unit::Decibels, Unit::Milliseconds] {
+            assert_eq!(unit.parse(&unit.format(1250.0)), Some(1250.0));
+        }
+    }
+
+    #[test]
+    fn test_number_field() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 100.0,
+            |value, _| number_field(value).unit(Unit::Hertz).range(20.0..=20000.0),
+        );
+        let value = StateHandle::<f32>::new(cx.view_id(&vec![0]));
+        cx.focus_next(&ui, false);
+
+        let mut path = vec![0];
+        let mut process = |cx: &mut Context, event: Event| {
+            ui.process(&event, &mut path, cx, &mut vec![]);
+        };
+
+        process(&mut cx, Event::Key(Key::ArrowUp));
+        assert_eq!(cx[value], 101.0);
+
+        cx.key_mods.shift = true;
+        process(&mut cx, Event::Key(Key::ArrowDown));
+        assert!((cx[value] - 100.9).abs() < 1e-4);
+        cx.key_mods.shift = false;
+
+        // Typing replaces the value.
+        process(&mut cx, Event::TextInput("2".into()));
+        process(&mut cx, Event::TextInput("k".into()));
+        process(&mut cx, Event::Key(Key::Enter));
+        assert_eq!(cx[value], 2000.0);
+
+        // Out of range values are clamped.
+        process(&mut cx, Event::TextInput("5".into()));
+        process(&mut cx, Event::Key(Key::Enter));
+        assert_eq!(cx[value], 20.0);
+
+        // Escape cancels.
+        process(&mut cx, Event::TextInput("300".into()));
+        process(&mut cx, Event::Key(Key::Escape));
+        assert_eq!(cx[value], 20.0);
+    }
+}
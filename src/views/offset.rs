@@ -19,15 +19,21 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
+        let id = cx.view_id(path);
+        let offset = match event {
+            Event::Anim { dt, .. } => cx.step_animated(id, self.offset, *dt),
+            _ => cx.animated(id, self.offset),
+        };
         path.push(0);
         self.child
-            .process(&event.offset(-self.offset), path, cx, actions);
+            .process(&event.offset(-offset), path, cx, actions);
         path.pop();
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let offset = self.offset(path, args.cx);
         args.vger.save();
-        args.vger.translate(self.offset);
+        args.vger.translate(offset);
         path.push(0);
         self.child.draw(path, args);
         path.pop();
@@ -42,14 +48,16 @@ where
     }
 
     fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let offset = self.offset(path, cx);
         path.push(0);
-        self.child.dirty(path, xform.pre_translate(self.offset), cx);
+        self.child.dirty(path, xform.pre_translate(offset), cx);
         path.pop();
     }
 
     fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let offset = self.offset(path, cx);
         path.push(0);
-        let hit_id = self.child.hittest(path, pt - self.offset, cx);
+        let hit_id = self.child.hittest(path, pt - offset, cx);
         path.pop();
         hit_id
     }
@@ -60,7 +68,22 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let offset = self.offset(path, cx);
+        path.push(0);
+        self.child
+            .focusables(path, xform.pre_translate(offset), cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
         path.push(0);
         self.child.gc(path, cx, map);
         path.pop();
@@ -86,6 +109,12 @@ where
     pub fn new(child: V, offset: LocalOffset) -> Self {
         Self { child, offset }
     }
+
+    /// The offset shown, which animates in `with_animation`.
+    fn offset(&self, path: &IdPath, cx: &mut Context) -> LocalOffset {
+        let id = cx.view_id(path);
+        cx.animated(id, self.offset)
+    }
 }
 
 impl<V> private::Sealed for Offset<V> {}
@@ -0,0 +1,144 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `opacity` modifier.
+///
+/// Each view in the subtree is faded on its own, so overlapping children
+/// show through each other. There's no compositing group mode, which
+/// would fade the subtree as a whole: that needs the subtree drawn into
+/// an offscreen texture, and vger can only draw into the frame and make
+/// images from pixels in memory.
+#[derive(Clone)]
+pub struct Opacity<V> {
+    child: V,
+    alpha: f32,
+}
+
+impl<V> Opacity<V>
+where
+    V: View,
+{
+    pub fn new(child: V, alpha: f32) -> Self {
+        Self {
+            child,
+            alpha: alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Fades the views drawn until `exit`. Returns the opacity to restore.
+    fn enter(&self, cx: &mut Context) -> f32 {
+        let saved = cx.opacity;
+        cx.opacity *= self.alpha;
+        saved
+    }
+
+    fn exit(cx: &mut Context, saved: f32) {
+        cx.opacity = saved;
+    }
+}
+
+impl<V> DynView for Opacity<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let saved = self.enter(args.cx);
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        Self::exit(args.cx, saved);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for Opacity<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_opacity() {
+        let mut cx = Context::new();
+        let color = Color::new(1.0, 0.0, 0.0, 0.8);
+
+        // Nested opacities multiply.
+        let outer = rectangle().opacity(0.5);
+        let inner = rectangle().opacity(0.5);
+        let saved = outer.enter(&mut cx);
+        let inner_saved = inner.enter(&mut cx);
+        assert_eq!(cx.faded(color).a, 0.8 * 0.25);
+        Opacity::<Rectangle>::exit(&mut cx, inner_saved);
+        assert_eq!(cx.faded(color).a, 0.8 * 0.5);
+        Opacity::<Rectangle>::exit(&mut cx, saved);
+        assert_eq!(cx.faded(color), color);
+    }
+}
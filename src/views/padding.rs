@@ -19,15 +19,21 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        let off = LocalOffset::new(self.padding, self.padding);
+        let id = cx.view_id(path);
+        let padding = match event {
+            Event::Anim { dt, .. } => cx.step_animated(id, self.padding, *dt),
+            _ => cx.animated(id, self.padding),
+        };
+        let off = LocalOffset::new(padding, padding);
         path.push(0);
         self.child.process(&event.offset(-off), path, cx, actions);
         path.pop();
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let padding = self.padding(path, args.cx);
         args.vger.save();
-        args.vger.translate([self.padding, self.padding]);
+        args.vger.translate([padding, padding]);
         path.push(0);
         self.child.draw(path, args);
         path.pop();
@@ -35,30 +41,30 @@ where
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let padding = self.padding(path, args.cx);
         path.push(0);
         let child_size = self.child.layout(
             path,
-            &mut args.size(args.sz - [2.0 * self.padding, 2.0 * self.padding].into()),
+            &mut args.size(args.sz - [2.0 * padding, 2.0 * padding].into()),
         );
         path.pop();
-        child_size + LocalSize::new(2.0 * self.padding, 2.0 * self.padding)
+        child_size + LocalSize::new(2.0 * padding, 2.0 * padding)
     }
 
     fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let padding = self.padding(path, cx);
         path.push(0);
-        self.child.dirty(
-            path,
-            xform.pre_translate([self.padding, self.padding].into()),
-            cx,
-        );
+        self.child
+            .dirty(path, xform.pre_translate([padding, padding].into()), cx);
         path.pop();
     }
 
     fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let padding = self.padding(path, cx);
         path.push(0);
-        let hit_id =
-            self.child
-                .hittest(path, pt - LocalOffset::new(self.padding, self.padding), cx);
+        let hit_id = self
+            .child
+            .hittest(path, pt - LocalOffset::new(padding, padding), cx);
         path.pop();
         hit_id
     }
@@ -69,7 +75,26 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let padding = self.padding(path, cx);
+        path.push(0);
+        self.child.focusables(
+            path,
+            xform.pre_translate([padding, padding].into()),
+            cx,
+            ids,
+        );
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
         path.push(0);
         self.child.gc(path, cx, map);
         path.pop();
@@ -117,6 +142,12 @@ where
             },
         }
     }
+
+    /// The padding shown, which animates in `with_animation`.
+    fn padding(&self, path: &IdPath, cx: &mut Context) -> f32 {
+        let id = cx.view_id(path);
+        cx.animated(id, self.padding)
+    }
 }
 
 impl<V> private::Sealed for Padding<V> {}
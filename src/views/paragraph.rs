@@ -0,0 +1,739 @@
+use crate::*;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+type TextBoundsFn<'a> = &'a mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect;
+
+/// Horizontal alignment of the lines of a paragraph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlignment {
+    #[default]
+    Leading,
+    Center,
+    Trailing,
+
+    /// Stretches the space between words so wrapped lines fill the width.
+    Justified,
+}
+
+/// Where text is cut, and replaced with an ellipsis, when it doesn't fit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Truncation {
+    Head,
+    Middle,
+    #[default]
+    Tail,
+}
+
+/// How text is broken into lines and placed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ParagraphStyle {
+    /// Wrap lines to fit the width. Otherwise lines only break at newlines.
+    pub wrap: bool,
+    pub line_limit: Option<usize>,
+    pub truncation: Truncation,
+    pub alignment: TextAlignment,
+
+    /// Height of a line, relative to the font size.
+    pub line_height: f32,
+
+    /// Extra space after each character.
+    pub letter_spacing: f32,
+}
+
+impl Default for ParagraphStyle {
+    fn default() -> Self {
+        Self {
+            wrap: true,
+            line_limit: None,
+            truncation: Truncation::Tail,
+            alignment: TextAlignment::Leading,
+            line_height: 1.2,
+            letter_spacing: 0.0,
+        }
+    }
+}
+
+/// Part of a span placed on one line.
+#[derive(Clone, Debug)]
+pub(crate) struct Fragment {
+    pub span: usize,
+    pub range: Range<usize>,
+    pub line: usize,
+    pub x: f32,
+    pub width: f32,
+
+    /// Distance from the top of the line to the baseline, for this span's size.
+    pub ascent: f32,
+
+    /// Does the fragment end between words, where justified text can stretch?
+    pub gap: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct LineBox {
+    /// Distance from the top of the paragraph.
+    pub top: f32,
+    pub height: f32,
+    pub ascent: f32,
+
+    /// Width without trailing whitespace.
+    pub width: f32,
+
+    /// Did the line end because the next word didn't fit?
+    pub wrapped: bool,
+}
+
+/// Where the spans of a paragraph are placed.
+///
+/// Keeps its own copy of the spans, since truncation adds an ellipsis.
+#[derive(Clone, Default)]
+pub(crate) struct ParagraphLayout {
+    pub spans: Vec<Span>,
+    pub size: LocalSize,
+    pub lines: Vec<LineBox>,
+    pub fragments: Vec<Fragment>,
+    pub letter_spacing: f32,
+}
+
+impl ParagraphLayout {
+    /// Bounds of a fragment, in the paragraph's local space.
+    pub fn fragment_rect(&self, fragment: &Fragment) -> LocalRect {
+        let line = self.lines[fragment.line];
+        LocalRect::new(
+            [fragment.x, self.size.height - line.top - line.height].into(),
+            [fragment.width, line.height].into(),
+        )
+    }
+
+    /// The span under a point.
+    pub fn span_at(&self, pt: LocalPoint) -> Option<&Span> {
+        self.fragments
+            .iter()
+            .find(|f| self.fragment_rect(f).contains(pt))
+            .map(|f| &self.spans[f.span])
+    }
+
+    /// Text of each line, for tests.
+    #[cfg(test)]
+    pub fn line_text(&self) -> Vec<String> {
+        (0..self.lines.len())
+            .map(|l| {
+                self.fragments
+                    .iter()
+                    .filter(|f| f.line == l)
+                    .map(|f| &self.spans[f.span].text[f.range.clone()])
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Break {
+    /// The piece continues into the next one, like a word split across spans.
+    None,
+
+    /// A line can wrap after the piece, which ends in whitespace.
+    Soft,
+
+    /// A newline follows the piece.
+    Hard,
+
+    /// The piece is part of a word too wide for a line, which wraps after it.
+    Split,
+}
+
+/// Font of a span whose font was resolved by `layout_paragraph`.
+fn span_font(span: &Span) -> Font {
+    span.font.resolve(&Font::default())
+}
+
+/// Color of a span whose color was resolved by `layout_paragraph`.
+fn span_color(span: &Span, cx: &Context) -> Color {
+    span.color.unwrap_or(cx.theme().text)
+}
+
+/// Distance the pen moves when drawing part of a span. Unlike the bounds,
+/// this includes trailing whitespace.
+fn advance(text: &str, span: &Span, letter_spacing: f32, text_bounds: TextBoundsFn) -> f32 {
+    text_advance(
+        text,
+        span.size,
+        &span_font(span),
+        letter_spacing,
+        text_bounds,
+    )
+}
+
+/// Splits the pieces of a word too wide for a line at grapheme boundaries,
+/// so that each part fits on a line. Returns false if the word is a single
+/// grapheme, which can't be split.
+fn split_word(
+    pieces: &mut Vec<(usize, Range<usize>, Break)>,
+    word: Range<usize>,
+    spans: &[Span],
+    max_width: f32,
+    letter_spacing: f32,
+    text_bounds: TextBoundsFn,
+) -> bool {
+    let mut parts = vec![];
+    let mut width = 0.0;
+    let mut split = false;
+    for (s, range, brk) in &pieces[word.clone()] {
+        let span = &spans[*s];
+        let mut start = range.start;
+        for (k, g) in span.text[range.clone()].grapheme_indices(true) {
+            let w = advance(g, span, letter_spacing, text_bounds);
+            // Trailing whitespace may hang past the end of the line.
+            if width > 0.0 && width + w > max_width && !g.trim().is_empty() {
+                let at = range.start + k;
+                if at > start {
+                    parts.push((*s, start..at, Break::None));
+                    start = at;
+                }
+                if let Some(part) = parts.last_mut() {
+                    part.2 = Break::Split;
+                }
+                width = 0.0;
+                split = true;
+            }
+            width += w;
+        }
+        parts.push((*s, start..range.end, *brk));
+    }
+    if split {
+        pieces.splice(word, parts);
+    }
+    split
+}
+
+/// Breaks spans into lines no wider than `max_width`, where possible.
+fn wrap_spans(
+    spans: Vec<Span>,
+    max_width: f32,
+    style: &ParagraphStyle,
+    text_bounds: TextBoundsFn,
+) -> ParagraphLayout {
+    let spacing = style.letter_spacing;
+
+    // Split the spans where lines may break.
+    let mut pieces = vec![];
+    for (i, span) in spans.iter().enumerate() {
+        let text = &span.text;
+        let mut start = 0;
+        for (j, c) in text.char_indices() {
+            let end = j + c.len_utf8();
+            let next_is_space = text[end..]
+                .chars()
+                .next()
+                .is_some_and(|n| n.is_whitespace() && n != '\n');
+            if c == '\n' {
+                pieces.push((i, start..j, Break::Hard));
+                start = end;
+            } else if c.is_whitespace() && !next_is_space {
+                pieces.push((i, start..end, Break::Soft));
+                start = end;
+            }
+        }
+        if start < text.len() {
+            pieces.push((i, start..text.len(), Break::None));
+        }
+    }
+
+    let mut ascents: Vec<(u32, Font, f32)> = vec![];
+    let mut ascent = |span: &Span, text_bounds: TextBoundsFn| {
+        let font = span_font(span);
+        if let Some((_, _, a)) = ascents
+            .iter()
+            .find(|(s, f, _)| *s == span.size && *f == font)
+        {
+            return *a;
+        }
+        let a = text_ascent(span.size, &font, text_bounds);
+        ascents.push((span.size, font, a));
+        a
+    };
+
+    let mut layout = ParagraphLayout {
+        letter_spacing: spacing,
+        ..Default::default()
+    };
+    let mut line_start = 0;
+    let mut x = 0.0;
+    let mut visible_width: f32 = 0.0;
+    let mut top = 0.0;
+
+    let mut finish_line = |layout: &mut ParagraphLayout, visible: f32, wrapped: bool| {
+        let fragments = &layout.fragments[line_start..];
+        let line_ascent = fragments.iter().fold(0.0f32, |a, f| a.max(f.ascent));
+        let descent = fragments.iter().fold(0.0f32, |d, f| {
+            d.max(spans[f.span].size as f32 * style.line_height - f.ascent)
+        });
+        layout.lines.push(LineBox {
+            top,
+            height: line_ascent + descent,
+            ascent: line_ascent,
+            width: visible,
+            wrapped,
+        });
+        top += line_ascent + descent;
+        layout.size.width = layout.size.width.max(visible);
+        line_start = layout.fragments.len();
+    };
+
+    let mut i = 0;
+    while i < pieces.len() {
+        // A word is the pieces up to the next break.
+        let mut j = i;
+        while pieces[j].2 == Break::None && j + 1 < pieces.len() {
+            j += 1;
+        }
+
+        let mut widths = vec![];
+        for (s, range, _) in &pieces[i..=j] {
+            let span = &spans[*s];
+            let text = &span.text[range.clone()];
+            widths.push(advance(text, span, spacing, text_bounds));
+        }
+
+        // Trailing whitespace may hang past the end of the line.
+        let (s, range, brk) = pieces[j].clone();
+        let last = &spans[s].text[range];
+        let trimmed = advance(last.trim_end(), &spans[s], spacing, text_bounds);
+        let hanging = widths[j - i] - trimmed;
+        let word_width = widths.iter().sum::<f32>() - hanging;
+
+        if word_width > max_width
+            && split_word(
+                &mut pieces,
+                i..j + 1,
+                &spans,
+                max_width,
+                spacing,
+                text_bounds,
+            )
+        {
+            continue;
+        }
+
+        if x > 0.0 && x + word_width > max_width {
+            finish_line(&mut layout, visible_width, true);
+            x = 0.0;
+        }
+
+        for (k, (s, range, _)) in pieces[i..=j].iter().enumerate() {
+            layout.fragments.push(Fragment {
+                span: *s,
+                range: range.clone(),
+                line: layout.lines.len(),
+                x,
+                width: widths[k],
+                ascent: ascent(&spans[*s], text_bounds),
+                gap: i + k == j && brk == Break::Soft,
+            });
+            x += widths[k];
+        }
+        visible_width = x - hanging;
+
+        if brk == Break::Hard {
+            finish_line(&mut layout, visible_width, false);
+            x = 0.0;
+        }
+        i = j + 1;
+    }
+
+    // Finish the last line, unless a newline already did.
+    if layout
+        .fragments
+        .last()
+        .is_some_and(|f| f.line == layout.lines.len())
+    {
+        finish_line(&mut layout, visible_width, false);
+    }
+    layout.size.height = top;
+    layout.spans = spans;
+    layout
+}
+
+/// Copies the part of the spans covering a range of graphemes.
+fn slice_spans(spans: &[Span], range: Range<usize>) -> Vec<Span> {
+    let mut sliced = vec![];
+    let mut index = 0;
+    for span in spans {
+        let bounds: Vec<usize> = span
+            .text
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(span.text.len()))
+            .collect();
+        let count = bounds.len() - 1;
+        let start = range.start.clamp(index, index + count) - index;
+        let end = range.end.clamp(index, index + count) - index;
+        if start < end {
+            let mut s = span.clone();
+            s.text = span.text[bounds[start]..bounds[end]].into();
+            sliced.push(s);
+        }
+        index += count;
+    }
+    sliced
+}
+
+/// An ellipsis in the style of a span.
+fn ellipsis(like: &Span) -> Span {
+    let mut s = like.clone();
+    s.text = "…".into();
+    s
+}
+
+/// Spans with all but `kept` graphemes replaced by an ellipsis.
+fn truncate_spans(spans: &[Span], total: usize, kept: usize, truncation: Truncation) -> Vec<Span> {
+    let style_at = |g: usize| {
+        slice_spans(spans, g..g + 1)
+            .pop()
+            .unwrap_or_else(|| spans[0].clone())
+    };
+    match truncation {
+        Truncation::Tail => {
+            let mut s = slice_spans(spans, 0..kept);
+            if let Some(last) = s.last_mut() {
+                last.text = last.text.trim_end().into();
+            }
+            s.push(ellipsis(&style_at(kept.saturating_sub(1))));
+            s
+        }
+        Truncation::Head => {
+            let mut s = slice_spans(spans, total - kept..total);
+            if let Some(first) = s.first_mut() {
+                first.text = first.text.trim_start().into();
+            }
+            s.insert(0, ellipsis(&style_at(total - kept)));
+            s
+        }
+        Truncation::Middle => {
+            let head = kept.div_ceil(2);
+            let mut s = slice_spans(spans, 0..head);
+            s.push(ellipsis(&style_at(head.saturating_sub(1))));
+            s.extend(slice_spans(spans, total - kept / 2..total));
+            s
+        }
+    }
+}
+
+/// Lays out spans as one paragraph, in the font and color inherited by spans
+/// which don't set their own.
+///
+/// Lines wrap between words to fit `max_width`. Words wider than that
+/// aren't broken. Text is only truncated with a line limit, or when it
+/// doesn't wrap.
+pub(crate) fn layout_paragraph(
+    spans: &[Span],
+    font: &Font,
+    color: Color,
+    max_width: f32,
+    style: &ParagraphStyle,
+    text_bounds: TextBoundsFn,
+) -> ParagraphLayout {
+    let spans: Vec<Span> = spans
+        .iter()
+        .map(|s| {
+            let mut s = s.clone();
+            s.font.set(s.font.resolve(font));
+            s.color.get_or_insert(color);
+            s
+        })
+        .collect();
+    let spans = &spans[..];
+    let wrap_width = if style.wrap { max_width } else { f32::INFINITY };
+    let limit = style.line_limit.unwrap_or(usize::MAX);
+    let fits = |p: &ParagraphLayout| p.lines.len() <= limit && p.size.width <= max_width + 0.5;
+
+    let mut layout = wrap_spans(spans.to_vec(), wrap_width, style, text_bounds);
+
+    let truncating = style.line_limit.is_some() || !style.wrap;
+    if truncating && !spans.is_empty() && !fits(&layout) {
+        let total: usize = spans.iter().map(|s| s.text.graphemes(true).count()).sum();
+
+        // Find how much text fits alongside the ellipsis.
+        let (mut lo, mut hi) = (0, total);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            let truncated = truncate_spans(spans, total, mid, style.truncation);
+            if fits(&wrap_spans(truncated, wrap_width, style, text_bounds)) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let truncated = truncate_spans(spans, total, lo, style.truncation);
+        layout = wrap_spans(truncated, wrap_width, style, text_bounds);
+    }
+
+    align_lines(&mut layout, style.alignment);
+    layout
+}
+
+fn align_lines(layout: &mut ParagraphLayout, alignment: TextAlignment) {
+    for (l, line) in layout.lines.iter().enumerate() {
+        let extra = layout.size.width - line.width;
+        let fragments = layout.fragments.iter_mut().filter(|f| f.line == l);
+        match alignment {
+            TextAlignment::Leading => (),
+            TextAlignment::Center => fragments.for_each(|f| f.x += extra / 2.0),
+            TextAlignment::Trailing => fragments.for_each(|f| f.x += extra),
+            TextAlignment::Justified => {
+                if !line.wrapped {
+                    continue;
+                }
+                let fragments: Vec<&mut Fragment> = fragments.collect();
+                let gaps = fragments.iter().rev().skip(1).filter(|f| f.gap).count();
+                if gaps == 0 {
+                    continue;
+                }
+                let mut shift = 0.0;
+                for f in fragments {
+                    f.x += shift;
+                    if f.gap {
+                        shift += extra / gaps as f32;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Draws a laid out paragraph, with its colors faded by `cx.faded`.
+pub(crate) fn draw_paragraph(paragraph: &ParagraphLayout, cx: &Context, vger: &mut Vger) {
+    let spacing = paragraph.letter_spacing;
+    for fragment in &paragraph.fragments {
+        let span = &paragraph.spans[fragment.span];
+        let text = &span.text[fragment.range.clone()];
+        let line = paragraph.lines[fragment.line];
+        let rect = paragraph.fragment_rect(fragment);
+        let baseline = rect.max_y() - line.ascent;
+        let size = span.size as f32;
+
+        if let Some(color) = span.background {
+            let paint = vger.color_paint(cx.faded(color));
+            vger.fill_rect(rect, 0.0, paint);
+        }
+
+        let font = span_font(span);
+        let origin = [fragment.x, baseline].into();
+        draw_text(
+            vger,
+            text,
+            span.size,
+            cx.faded(span_color(span, cx)),
+            &font,
+            origin,
+            spacing,
+        );
+
+        if span.underline || span.strikethrough {
+            let thickness = (size / 16.0).max(1.0);
+            let paint = vger.color_paint(cx.faded(span_color(span, cx)));
+            let width = advance(text.trim_end(), span, spacing, &mut |t, s, w| {
+                vger.text_bounds(t, s, w)
+            });
+            if span.underline {
+                let underline = LocalRect::new(
+                    [fragment.x, baseline - 2.0 * thickness].into(),
+                    [width, thickness].into(),
+                );
+                vger.fill_rect(underline, 0.0, paint);
+            }
+            if span.strikethrough {
+                let strike = LocalRect::new(
+                    [fragment.x, baseline + fragment.ascent * 0.3].into(),
+                    [width, thickness].into(),
+                );
+                vger.fill_rect(strike, 0.0, paint);
+            }
+        }
+    }
+}
+
+/// Reads the layout a text view stored for its id.
+pub(crate) fn stored_paragraph(id: ViewId, cx: &Context) -> Option<&ParagraphLayout> {
+    if cx.state_map.contains_key(&id) {
+        Some(&cx[StateHandle::<ParagraphLayout>::new(id)])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use super::*;
+
+    /// Every character is half the font size wide, and "H" is 0.8 of it tall.
+    pub(crate) fn bounds(text: &str, size: u32, _max_width: Option<f32>) -> LocalRect {
+        let size = size as f32;
+        LocalRect::new(
+            [0.0, -0.8 * size].into(),
+            [text.chars().count() as f32 * size * 0.5, 0.8 * size].into(),
+        )
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let spans = [
+            span("aa bb").font_size(10),
+            span("cc dd ").font_size(20),
+            span("ee\nff").font_size(10),
+        ];
+        let p = layout_paragraph(
+            &spans,
+            &Font::default(),
+            TEXT_COLOR,
+            40.0,
+            &ParagraphStyle::default(),
+            &mut bounds,
+        );
+
+        // "bbcc" has no space between spans, so it stays together.
+        assert_eq!(p.line_text(), ["aa ", "bbcc ", "dd ee", "ff"]);
+
+        // Lines with the larger span are taller, and baselines line up.
+        assert_eq!(p.lines[0].height, 12.0);
+        assert_eq!(p.lines[1].height, 24.0);
+        assert_eq!(p.lines[1].ascent, 16.0);
+        assert_eq!(p.size.height, 12.0 + 24.0 + 24.0 + 12.0);
+        assert_eq!(p.size.width, 40.0);
+    }
+
+    #[test]
+    fn test_long_words() {
+        // Characters are 10 wide, and "e\u{301}" is a single grapheme of two.
+        let spans = [span("ab abe\u{301}cdefg hi").font_size(20)];
+        let p = layout_paragraph(
+            &spans,
+            &Font::default(),
+            TEXT_COLOR,
+            40.0,
+            &ParagraphStyle::default(),
+            &mut bounds,
+        );
+        assert_eq!(p.line_text(), ["ab ", "abe\u{301}", "cdef", "g hi"]);
+        assert!(p.size.width <= 40.0);
+
+        // A single grapheme wider than the line overflows.
+        let spans = [span("w").font_size(100)];
+        let p = layout_paragraph(
+            &spans,
+            &Font::default(),
+            TEXT_COLOR,
+            40.0,
+            &ParagraphStyle::default(),
+            &mut bounds,
+        );
+        assert_eq!(p.line_text(), ["w"]);
+    }
+
+    #[test]
+    fn test_truncation() {
+        // 10 wide characters.
+        let spans = [span("one two three four").font_size(20)];
+        let layout = |style: ParagraphStyle, width: f32| {
+            layout_paragraph(
+                &spans,
+                &Font::default(),
+                TEXT_COLOR,
+                width,
+                &style,
+                &mut bounds,
+            )
+            .line_text()
+        };
+
+        let one_line = ParagraphStyle {
+            line_limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(layout(one_line, 80.0), ["one two…"]);
+        let head = ParagraphStyle {
+            truncation: Truncation::Head,
+            ..one_line
+        };
+        assert_eq!(layout(head, 80.0), ["…ee four"]);
+        let middle = ParagraphStyle {
+            truncation: Truncation::Middle,
+            ..one_line
+        };
+        assert_eq!(layout(middle, 80.0), ["one …our"]);
+
+        let two_lines = ParagraphStyle {
+            line_limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(layout(two_lines, 90.0), ["one two ", "three fo…"]);
+
+        // Text which fits isn't changed.
+        assert_eq!(layout(one_line, 1000.0), ["one two three four"]);
+    }
+
+    #[test]
+    fn test_alignment() {
+        let spans = [span("aa bb cccc").font_size(20)];
+        let align = |alignment| {
+            let style = ParagraphStyle {
+                alignment,
+                ..Default::default()
+            };
+            let p = layout_paragraph(
+                &spans,
+                &Font::default(),
+                TEXT_COLOR,
+                60.0,
+                &style,
+                &mut bounds,
+            );
+            p.fragments.iter().map(|f| f.x).collect::<Vec<_>>()
+        };
+
+        // Lines are "aa bb " (50 wide) and "cccc" (40 wide).
+        assert_eq!(align(TextAlignment::Leading), [0.0, 30.0, 0.0]);
+        assert_eq!(align(TextAlignment::Center), [0.0, 30.0, 5.0]);
+        assert_eq!(align(TextAlignment::Trailing), [0.0, 30.0, 10.0]);
+
+        // The wrapped line stretches to the widest line, but the last doesn't.
+        let spans = [span("aa b cccccc").font_size(20)];
+        let style = ParagraphStyle {
+            alignment: TextAlignment::Justified,
+            ..Default::default()
+        };
+        let p = layout_paragraph(
+            &spans,
+            &Font::default(),
+            TEXT_COLOR,
+            60.0,
+            &style,
+            &mut bounds,
+        );
+        let x: Vec<f32> = p.fragments.iter().map(|f| f.x).collect();
+        assert_eq!(x, [0.0, 50.0, 0.0]);
+    }
+
+    #[test]
+    fn test_letter_spacing() {
+        let spans = [span("ab cd").font_size(20)];
+        let style = ParagraphStyle {
+            letter_spacing: 2.0,
+            line_height: 2.0,
+            ..Default::default()
+        };
+        let p = layout_paragraph(
+            &spans,
+            &Font::default(),
+            TEXT_COLOR,
+            1000.0,
+            &style,
+            &mut bounds,
+        );
+        assert_eq!(p.size.width, 36.0 + 24.0);
+        assert_eq!(p.size.height, 40.0);
+    }
+}
@@ -0,0 +1,609 @@
+use crate::*;
+use std::any::Any;
+use std::f32::consts::PI;
+
+/// Segments used to flatten each curve in a path.
+const CURVE_STEPS: usize = 16;
+
+/// Segments used to flatten a full turn of an ellipse or arc.
+const TURN_STEPS: usize = 64;
+
+/// A polyline along part of a shape's outline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contour {
+    points: Vec<LocalPoint>,
+    closed: bool,
+}
+
+/// Outline of a `Shape`, in the shape's local space.
+pub trait Geometry: Clone + 'static + private::Sealed {
+    /// The outline of the shape filling `rect`, which is stroked.
+    fn contours(&self, rect: LocalRect) -> Vec<Contour>;
+
+    /// The regions filled, each closed back to its start.
+    fn fill_contours(&self, rect: LocalRect) -> Vec<Contour> {
+        self.contours(rect)
+    }
+
+    /// Whether the shape encloses anything to fill.
+    fn fills(&self) -> bool {
+        true
+    }
+}
+
+/// Maps a point relative to the size of `rect` into it.
+fn place(rect: LocalRect, pt: LocalPoint) -> LocalPoint {
+    rect.origin + LocalOffset::new(pt.x * rect.width(), pt.y * rect.height())
+}
+
+/// Points around an arc of the ellipse filling `rect`, counterclockwise
+/// from `start` through `sweep` radians.
+fn arc_points(rect: LocalRect, start: f32, sweep: f32) -> Vec<LocalPoint> {
+    let steps = ((sweep.abs() / (2.0 * PI) * TURN_STEPS as f32).ceil() as usize).max(1);
+    let center = rect.center();
+    let (rx, ry) = (rect.width() / 2.0, rect.height() / 2.0);
+    (0..=steps)
+        .map(|i| {
+            let theta = start + sweep * i as f32 / steps as f32;
+            center + LocalOffset::new(rx * theta.cos(), ry * theta.sin())
+        })
+        .collect()
+}
+
+/// Struct for `ellipse`.
+#[derive(Clone, Debug)]
+pub struct Ellipse {}
+
+impl Geometry for Ellipse {
+    fn contours(&self, rect: LocalRect) -> Vec<Contour> {
+        let mut points = arc_points(rect, 0.0, 2.0 * PI);
+        points.pop();
+        vec![Contour {
+            points,
+            closed: true,
+        }]
+    }
+}
+
+impl private::Sealed for Ellipse {}
+
+/// Struct for `capsule`.
+#[derive(Clone, Debug)]
+pub struct Capsule {}
+
+impl Geometry for Capsule {
+    fn contours(&self, rect: LocalRect) -> Vec<Contour> {
+        let radius = rect.width().min(rect.height()) / 2.0;
+        vec![Contour {
+            points: rounded_rect_points(rect, radius),
+            closed: true,
+        }]
+    }
+}
+
+impl private::Sealed for Capsule {}
+
+/// Struct for `arc`.
+#[derive(Clone, Debug)]
+pub struct CircularArc {
+    start: f32,
+    sweep: f32,
+}
+
+impl CircularArc {
+    /// The square centered in `rect`, which the arc's circle fills.
+    fn square(rect: LocalRect) -> LocalRect {
+        let side = rect.width().min(rect.height());
+        LocalRect::new(
+            rect.center() - LocalOffset::new(side, side) / 2.0,
+            [side, side].into(),
+        )
+    }
+}
+
+impl Geometry for CircularArc {
+    fn contours(&self, rect: LocalRect) -> Vec<Contour> {
+        vec![Contour {
+            points: arc_points(Self::square(rect), self.start, self.sweep),
+            closed: false,
+        }]
+    }
+
+    /// Arcs fill the pie slice between them and the center.
+    fn fill_contours(&self, rect: LocalRect) -> Vec<Contour> {
+        let mut points = vec![rect.center()];
+        points.extend(arc_points(Self::square(rect), self.start, self.sweep));
+        vec![Contour {
+            points,
+            closed: true,
+        }]
+    }
+}
+
+impl private::Sealed for CircularArc {}
+
+/// Struct for `line`.
+#[derive(Clone, Debug)]
+pub struct Line {
+    from: LocalPoint,
+    to: LocalPoint,
+}
+
+impl Geometry for Line {
+    fn contours(&self, rect: LocalRect) -> Vec<Contour> {
+        vec![Contour {
+            points: vec![place(rect, self.from), place(rect, self.to)],
+            closed: false,
+        }]
+    }
+
+    fn fill_contours(&self, _rect: LocalRect) -> Vec<Contour> {
+        vec![]
+    }
+
+    fn fills(&self) -> bool {
+        false
+    }
+}
+
+impl private::Sealed for Line {}
+
+/// Struct for `polygon`.
+#[derive(Clone, Debug)]
+pub struct Polygon {
+    points: Vec<LocalPoint>,
+}
+
+impl Geometry for Polygon {
+    fn contours(&self, rect: LocalRect) -> Vec<Contour> {
+        vec![Contour {
+            points: self.points.iter().map(|p| place(rect, *p)).collect(),
+            closed: true,
+        }]
+    }
+}
+
+impl private::Sealed for Polygon {}
+
+#[derive(Clone, Copy, Debug)]
+enum PathCommand {
+    MoveTo(LocalPoint),
+    LineTo(LocalPoint),
+    QuadTo(LocalPoint, LocalPoint),
+    CubicTo(LocalPoint, LocalPoint, LocalPoint),
+    Close,
+}
+
+/// Struct for `path`.
+#[derive(Clone, Debug, Default)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+}
+
+impl Geometry for Path {
+    fn contours(&self, rect: LocalRect) -> Vec<Contour> {
+        let mut contours = vec![];
+        let mut points = vec![];
+        let mut finish = |points: &mut Vec<LocalPoint>, closed| {
+            if points.len() > 1 {
+                contours.push(Contour {
+                    points: points.clone(),
+                    closed,
+                });
+            }
+            // A closed subpath leaves the pen where it started.
+            let start = points.first().copied();
+            points.clear();
+            points.extend(start.filter(|_| closed));
+        };
+
+        for command in &self.commands {
+            let pen = points.last().copied().unwrap_or(rect.origin);
+            match *command {
+                PathCommand::MoveTo(p) => {
+                    finish(&mut points, false);
+                    points.push(place(rect, p));
+                }
+                PathCommand::LineTo(p) => {
+                    if points.is_empty() {
+                        points.push(pen);
+                    }
+                    points.push(place(rect, p));
+                }
+                PathCommand::QuadTo(c, p) => {
+                    if points.is_empty() {
+                        points.push(pen);
+                    }
+                    let (c, p) = (place(rect, c), place(rect, p));
+                    points.extend((1..=CURVE_STEPS).map(|i| {
+                        let t = i as f32 / CURVE_STEPS as f32;
+                        pen.lerp(c, t).lerp(c.lerp(p, t), t)
+                    }));
+                }
+                PathCommand::CubicTo(c1, c2, p) => {
+                    if points.is_empty() {
+                        points.push(pen);
+                    }
+                    let (c1, c2, p) = (place(rect, c1), place(rect, c2), place(rect, p));
+                    points.extend((1..=CURVE_STEPS).map(|i| {
+                        let t = i as f32 / CURVE_STEPS as f32;
+                        let a = pen.lerp(c1, t).lerp(c1.lerp(c2, t), t);
+                        let b = c1.lerp(c2, t).lerp(c2.lerp(p, t), t);
+                        a.lerp(b, t)
+                    }));
+                }
+                PathCommand::Close => finish(&mut points, true),
+            }
+        }
+        finish(&mut points, false);
+        contours
+    }
+}
+
+impl private::Sealed for Path {}
+
+/// A shape drawn from a `Geometry`, which expands to fill available space.
+/// Points are relative to the shape's size, so `[0.0, 0.0]` is its lower
+/// left corner and `[1.0, 1.0]` its upper right.
+#[derive(Clone)]
+pub struct Shape<G> {
+    geometry: G,
+    style: ShapeStyle,
+}
+
+impl<G> Shape<G>
+where
+    G: Geometry,
+{
+    /// Sets the fill color.
+    pub fn color(self, color: Color) -> Self {
+        self.fill(color)
+    }
+
+    /// Sets the fill. It's drawn under the stroke, if there is one.
+    pub fn fill(mut self, paint: impl Into<Paint>) -> Self {
+        self.style.fill = Some(paint.into());
+        self
+    }
+
+    /// Outlines the shape, inside its bounds. Unless a fill is also set,
+    /// the shape is only outlined.
+    pub fn stroke(mut self, paint: impl Into<Paint>, width: f32) -> Self {
+        self.style.stroke = Some((paint.into(), width));
+        self
+    }
+
+    /// Dashes the stroke, alternating dashes and gaps of the given
+    /// lengths.
+    pub fn dash(mut self, dash: f32, gap: f32) -> Self {
+        self.style.dash = Some((dash, gap));
+        self
+    }
+
+    /// The stroke's paint and width. Shapes which don't fill are stroked
+    /// even without one.
+    fn stroke_style(&self) -> Option<(&Paint, f32)> {
+        match &self.style.stroke {
+            Some((paint, width)) => Some((paint, *width)),
+            None if !self.geometry.fills() => Some((&DEFAULT_FILL, 1.0)),
+            None => None,
+        }
+    }
+
+    /// The contours stroked, inset so the stroke stays inside `rect`.
+    fn stroke_contours(&self, rect: LocalRect, width: f32) -> Vec<Contour> {
+        self.geometry
+            .contours(rect.inflate(-width / 2.0, -width / 2.0))
+    }
+
+    fn geom(&self, path: &IdPath, cx: &mut Context) -> LocalRect {
+        cx.get_layout(path).rect
+    }
+}
+
+impl<G> DynView for Shape<G>
+where
+    G: Geometry,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        _actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim { dt, .. } = event {
+            let id = cx.view_id(path);
+            self.style.step(id, cx, *dt);
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = self.geom(path, args.cx);
+
+        let id = args.cx.view_id(path);
+        if self.geometry.fills() {
+            if let Some(paint) = self.style.fill_paint(id, args) {
+                for contour in self.geometry.fill_contours(rect) {
+                    if let Some((first, rest)) = contour.points.split_first() {
+                        let mut pen = *first;
+                        args.vger.move_to(pen);
+                        for p in rest.iter().chain(std::iter::once(first)) {
+                            args.vger.quad_to(pen.lerp(*p, 0.5), *p);
+                            pen = *p;
+                        }
+                    }
+                }
+                args.vger.fill(paint);
+            }
+        }
+
+        if let Some((paint, width)) = self.stroke_style() {
+            let paint = paint.faded(args.cx).vger_paint(args.vger);
+            for contour in self.stroke_contours(rect, width) {
+                stroke_polyline(
+                    args.vger,
+                    &contour.points,
+                    contour.closed,
+                    width,
+                    self.style.dash,
+                    paint,
+                );
+            }
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let rect = self.geom(path, cx);
+
+        let filled = self.geometry.fills()
+            && self.style.fill().is_some()
+            && self
+                .geometry
+                .fill_contours(rect)
+                .iter()
+                .map(|contour| winding(&contour.points, pt))
+                .sum::<i32>()
+                != 0;
+
+        let stroked = || match self.stroke_style() {
+            Some((_, width)) => self
+                .stroke_contours(rect, width)
+                .iter()
+                .any(|contour| distance_to_contour(contour, pt) <= width / 2.0),
+            None => false,
+        };
+
+        if filled || stroked() {
+            Some(cx.view_id(path))
+        } else {
+            None
+        }
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+    }
+}
+
+impl<G> private::Sealed for Shape<G> {}
+
+impl Shape<Path> {
+    /// Starts a new subpath at `p`.
+    pub fn move_to(mut self, p: impl Into<LocalPoint>) -> Self {
+        self.geometry.commands.push(PathCommand::MoveTo(p.into()));
+        self
+    }
+
+    /// Adds a straight line to `p`.
+    pub fn line_to(mut self, p: impl Into<LocalPoint>) -> Self {
+        self.geometry.commands.push(PathCommand::LineTo(p.into()));
+        self
+    }
+
+    /// Adds a quadratic curve to `p`, bending toward `control`.
+    pub fn quad_to(mut self, control: impl Into<LocalPoint>, p: impl Into<LocalPoint>) -> Self {
+        self.geometry
+            .commands
+            .push(PathCommand::QuadTo(control.into(), p.into()));
+        self
+    }
+
+    /// Adds a cubic curve to `p`, bending toward the two control points.
+    pub fn cubic_to(
+        mut self,
+        control1: impl Into<LocalPoint>,
+        control2: impl Into<LocalPoint>,
+        p: impl Into<LocalPoint>,
+    ) -> Self {
+        self.geometry.commands.push(PathCommand::CubicTo(
+            control1.into(),
+            control2.into(),
+            p.into(),
+        ));
+        self
+    }
+
+    /// Closes the subpath with a line back to its start.
+    pub fn close(mut self) -> Self {
+        self.geometry.commands.push(PathCommand::Close);
+        self
+    }
+}
+
+fn shape<G: Geometry>(geometry: G) -> Shape<G> {
+    Shape {
+        geometry,
+        style: ShapeStyle::default(),
+    }
+}
+
+/// Renders an ellipse which expands to fill available space.
+pub fn ellipse() -> Shape<Ellipse> {
+    shape(Ellipse {})
+}
+
+/// Renders a rectangle with fully rounded ends, which expands to fill
+/// available space.
+pub fn capsule() -> Shape<Capsule> {
+    shape(Capsule {})
+}
+
+/// Renders an arc of the largest circle which fits, counterclockwise from
+/// `start` through `sweep` radians. Filling it fills the pie slice.
+pub fn arc(start: f32, sweep: f32) -> Shape<CircularArc> {
+    shape(CircularArc { start, sweep })
+}
+
+/// Renders a straight line. It's stroked, one point wide unless
+/// `stroke` says otherwise.
+pub fn line(from: impl Into<LocalPoint>, to: impl Into<LocalPoint>) -> Shape<Line> {
+    shape(Line {
+        from: from.into(),
+        to: to.into(),
+    })
+}
+
+/// Renders a closed polygon through `points`.
+pub fn polygon<P: Into<LocalPoint>>(points: impl IntoIterator<Item = P>) -> Shape<Polygon> {
+    shape(Polygon {
+        points: points.into_iter().map(|p| p.into()).collect(),
+    })
+}
+
+/// Starts an empty path, built up with `move_to`, `line_to`, `quad_to`,
+/// `cubic_to` and `close`.
+pub fn path() -> Shape<Path> {
+    shape(Path::default())
+}
+
+/// Winding number of a closed polyline around `pt`. It's nonzero inside.
+fn winding(points: &[LocalPoint], pt: LocalPoint) -> i32 {
+    let mut winding = 0;
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        let side = (b - *a).cross(pt - *a);
+        if a.y <= pt.y && b.y > pt.y && side > 0.0 {
+            winding += 1;
+        } else if a.y > pt.y && b.y <= pt.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// Distance from `pt` to the nearest point along a contour.
+fn distance_to_contour(contour: &Contour, pt: LocalPoint) -> f32 {
+    let n = contour.points.len();
+    let segments = if contour.closed {
+        n
+    } else {
+        n.saturating_sub(1)
+    };
+    (0..segments)
+        .map(|i| {
+            let a = contour.points[i];
+            let ab = contour.points[(i + 1) % n] - a;
+            let t = if ab.square_length() > 0.0 {
+                ((pt - a).dot(ab) / ab.square_length()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            pt.distance_to(a + ab * t)
+        })
+        .fold(f32::MAX, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn hits(ui: &impl View, pt: [f32; 2]) -> bool {
+        let mut cx = Context::new();
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 50.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        ui.hittest(&mut path, pt.into(), &mut cx).is_some()
+    }
+
+    #[test]
+    fn test_hittest() {
+        // Corners of the bounds are outside the rounded shapes.
+        assert!(hits(&ellipse(), [50.0, 25.0]));
+        assert!(!hits(&ellipse(), [5.0, 5.0]));
+        assert!(hits(&capsule(), [90.0, 25.0]));
+        assert!(!hits(&capsule(), [99.0, 1.0]));
+
+        // Outlines only hit along the stroke.
+        let ring = ellipse().stroke(BLACK, 4.0);
+        assert!(hits(&ring, [2.0, 25.0]));
+        assert!(!hits(&ring, [50.0, 25.0]));
+
+        // Lines are stroked even without a stroke.
+        let diagonal = line([0.0, 0.0], [1.0, 1.0]);
+        assert!(hits(&diagonal, [50.0, 25.0]));
+        assert!(!hits(&diagonal, [50.0, 40.0]));
+
+        // A quarter pie, upper right of the center.
+        let pie = arc(0.0, PI / 2.0);
+        assert!(hits(&pie, [60.0, 35.0]));
+        assert!(!hits(&pie, [40.0, 35.0]));
+
+        let triangle = polygon([[0.0, 0.0], [1.0, 0.0], [0.5, 1.0]]);
+        assert!(hits(&triangle, [50.0, 40.0]));
+        assert!(!hits(&triangle, [10.0, 40.0]));
+    }
+
+    #[test]
+    fn test_path_contours() {
+        let rect = LocalRect::new(LocalPoint::zero(), [10.0, 10.0].into());
+
+        // Two subpaths, the first closed back to its start.
+        let ui = path()
+            .move_to([0.0, 0.0])
+            .line_to([1.0, 0.0])
+            .quad_to([1.0, 1.0], [0.0, 1.0])
+            .close()
+            .move_to([0.5, 0.5])
+            .cubic_to([0.6, 0.6], [0.7, 0.6], [0.8, 0.5]);
+        let contours = ui.geometry.contours(rect);
+        assert_eq!(contours.len(), 2);
+        assert!(contours[0].closed);
+        assert_eq!(contours[0].points.len(), 2 + CURVE_STEPS);
+        assert_eq!(contours[0].points[CURVE_STEPS + 1], [0.0, 10.0].into());
+        assert!(!contours[1].closed);
+        let end = *contours[1].points.last().unwrap();
+        assert!((end - LocalPoint::new(8.0, 5.0)).length() < 1e-5);
+
+        // Drawing continues from the start of a closed subpath.
+        let ui = path()
+            .move_to([0.0, 0.0])
+            .line_to([1.0, 0.0])
+            .line_to([1.0, 1.0])
+            .close()
+            .line_to([0.0, 1.0]);
+        let contours = ui.geometry.contours(rect);
+        assert_eq!(
+            contours[1].points,
+            vec![[0.0, 0.0].into(), [0.0, 10.0].into()]
+        );
+    }
+}
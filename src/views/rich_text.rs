@@ -0,0 +1,367 @@
+use crate::*;
+use std::any::Any;
+use std::sync::Arc;
+
+type SpanTapFn = Arc<dyn Fn(&mut Context)>;
+
+/// A run of text with its own style, part of an `AttributedString`.
+#[derive(Clone)]
+pub struct Span {
+    pub(crate) text: String,
+    pub(crate) size: u32,
+    /// The theme's text color unless set.
+    pub(crate) color: Option<Color>,
+    pub(crate) font: FontAttributes,
+    pub(crate) underline: bool,
+    pub(crate) strikethrough: bool,
+    pub(crate) background: Option<Color>,
+    pub(crate) on_tap: Option<SpanTapFn>,
+}
+
+impl Span {
+    pub fn font_size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the whole font, rather than inheriting parts of it from the environment.
+    pub fn font(mut self, font: Font) -> Self {
+        self.font.set(font);
+        self
+    }
+
+    pub fn font_family(mut self, family: impl Into<FontFamily>) -> Self {
+        self.font.family = Some(family.into());
+        self
+    }
+
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.font.weight = Some(weight);
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.font.italic = Some(true);
+        self
+    }
+
+    pub fn monospaced(self) -> Self {
+        self.font_family(FontFamily::Monospace)
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Highlights the span with a background color.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Calls a function when the span is tapped, for inline links.
+    pub fn on_tap(mut self, f: impl Fn(&mut Context) + 'static) -> Self {
+        self.on_tap = Some(Arc::new(f));
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Creates a run of text for an `AttributedString`, in the default style.
+pub fn span(text: &str) -> Span {
+    Span {
+        text: text.into(),
+        size: Text::DEFAULT_SIZE,
+        color: None,
+        font: FontAttributes::default(),
+        underline: false,
+        strikethrough: false,
+        background: None,
+        on_tap: None,
+    }
+}
+
+/// Text made of differently styled spans, shown with `rich_text`.
+///
+/// ```no_run
+/// # use rui::*;
+/// let s = AttributedString::new()
+///     .span(span("Error: ").color(RED_HIGHLIGHT).bold())
+///     .span(span("file not found. "))
+///     .span(span("Help").underline().on_tap(|_| println!("help")));
+/// ```
+#[derive(Clone, Default)]
+pub struct AttributedString {
+    spans: Vec<Span>,
+}
+
+impl AttributedString {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a span, builder style.
+    pub fn span(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn push(&mut self, span: Span) {
+        self.spans.push(span);
+    }
+
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// The text without styling.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|s| s.text.as_str()).collect()
+    }
+}
+
+impl From<Span> for AttributedString {
+    fn from(span: Span) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl From<Vec<Span>> for AttributedString {
+    fn from(spans: Vec<Span>) -> Self {
+        Self { spans }
+    }
+}
+
+impl From<&str> for AttributedString {
+    fn from(text: &str) -> Self {
+        span(text).into()
+    }
+}
+
+impl From<String> for AttributedString {
+    fn from(text: String) -> Self {
+        span(&text).into()
+    }
+}
+
+/// Struct for `rich_text`.
+#[derive(Clone)]
+pub struct RichText {
+    text: AttributedString,
+    max_width: Option<f32>,
+    style: ParagraphStyle,
+}
+
+impl RichText {
+    /// Wraps lines at this width, or the width offered, if less.
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Wraps lines to fit the width offered. On by default.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.style.wrap = wrap;
+        self
+    }
+
+    /// Limits the number of lines, truncating the text beyond.
+    pub fn line_limit(mut self, lines: usize) -> Self {
+        self.style.line_limit = Some(lines);
+        self
+    }
+
+    pub fn truncation(mut self, truncation: Truncation) -> Self {
+        self.style.truncation = truncation;
+        self
+    }
+
+    pub fn alignment(mut self, alignment: TextAlignment) -> Self {
+        self.style.alignment = alignment;
+        self
+    }
+
+    /// Height of lines, relative to the font size.
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.style.line_height = line_height;
+        self
+    }
+
+    /// Extra space between characters.
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.style.letter_spacing = letter_spacing;
+        self
+    }
+}
+
+impl DynView for RichText {
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        _actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        match &event {
+            Event::TouchBegin { id, position } if self.hittest(path, *position, cx).is_some() => {
+                cx.touches[*id] = vid;
+            }
+            Event::TouchEnd { id, position } if cx.touches[*id] == vid => {
+                cx.touches[*id] = ViewId::default();
+                let tap = stored_paragraph(vid, cx)
+                    .and_then(|p| p.span_at(*position))
+                    .and_then(|s| s.on_tap.clone());
+                if let Some(f) = tap.filter(|_| !cx.is_disabled()) {
+                    f(cx)
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let id = args.cx.view_id(path);
+        if let Some(paragraph) = stored_paragraph(id, args.cx) {
+            draw_paragraph(paragraph, args.cx, args.vger);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let max_width = match self.max_width {
+            Some(w) => w.min(args.sz.width),
+            None => args.sz.width,
+        };
+        let font = args.cx.init_env(&Font::default);
+        let paragraph = layout_paragraph(
+            &self.text.spans,
+            &font,
+            args.cx.theme().text,
+            max_width,
+            &self.style,
+            args.text_bounds,
+        );
+        let size = paragraph.size;
+        let id = args.cx.view_id(path);
+        args.cx.set_state(id, paragraph);
+        size
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        // Only tappable spans take touches.
+        let id = cx.view_id(path);
+        let span = stored_paragraph(id, cx)?.span_at(pt)?;
+        span.on_tap.as_ref().map(|_| id)
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        // Tappable spans are links within the label.
+        let mut links = vec![];
+        for (i, span) in self.text.spans.iter().enumerate() {
+            if span.on_tap.is_some() {
+                path.push(i as u64);
+                let aid = cx.view_id(path).access_id();
+                path.pop();
+                let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Link);
+                builder.set_name(span.text.clone());
+                nodes.push((aid, builder.build()));
+                links.push(aid);
+            }
+        }
+
+        let aid = cx.view_id(path).access_id();
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Label);
+        builder.set_name(self.text.plain_text());
+        builder.set_children(links);
+        nodes.push((aid, builder.build()));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for RichText {}
+
+/// Shows differently styled spans of text as one wrapped paragraph.
+pub fn rich_text(text: impl Into<AttributedString>) -> RichText {
+    RichText {
+        text: text.into(),
+        max_width: None,
+        style: ParagraphStyle::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::views::paragraph::tests::bounds;
+
+    #[test]
+    fn test_span_tap() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |taps, _| {
+                rich_text(
+                    AttributedString::new()
+                        .span(span("see "))
+                        .span(span("link").on_tap(move |cx| cx[taps] += 1)),
+                )
+            },
+        );
+        let taps = StateHandle::<i32>::new(cx.view_id(&vec![0]));
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [1000.0, 1000.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut bounds,
+            },
+        );
+
+        let mut tap = |cx: &mut Context, x: f32| {
+            let position = [x, 10.0].into();
+            for event in [
+                Event::TouchBegin { id: 0, position },
+                Event::TouchEnd { id: 0, position },
+            ] {
+                ui.process(&event, &mut path, cx, &mut vec![]);
+            }
+        };
+
+        // "see " is 36 wide.
+        tap(&mut cx, 10.0);
+        assert_eq!(cx[taps], 0);
+        tap(&mut cx, 50.0);
+        assert_eq!(cx[taps], 1);
+    }
+}
@@ -66,6 +66,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(0);
         self.child.gc(path, cx, map);
@@ -83,6 +95,9 @@ where
         path.pop();
         let aid = cx.view_id(path).access_id();
         let mut builder = accesskit::NodeBuilder::new(self.role);
+        if cx.is_disabled() {
+            builder.set_disabled();
+        }
         builder.set_children(match child_aid {
             Some(cid) => vec![cid],
             None => vec![],
@@ -0,0 +1,148 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `shadow` modifier.
+#[derive(Clone)]
+pub struct Shadow<V> {
+    child: V,
+    color: Color,
+    radius: f32,
+    offset: LocalOffset,
+    corner_radius: f32,
+}
+
+impl<V> Shadow<V>
+where
+    V: View,
+{
+    pub fn new(child: V, color: Color, radius: f32, offset: LocalOffset) -> Self {
+        Self {
+            child,
+            color,
+            radius,
+            offset,
+            corner_radius: 0.0,
+        }
+    }
+
+    /// Rounds the corners of the shadow, to match a rounded view.
+    pub fn corner_radius(self, corner_radius: f32) -> Self {
+        Self {
+            corner_radius,
+            ..self
+        }
+    }
+}
+
+impl<V> DynView for Shadow<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        draw_shadow(
+            rect,
+            self.corner_radius,
+            self.color,
+            self.radius,
+            self.offset,
+            args,
+        );
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for Shadow<V> {}
+
+/// Draws a blurred rounded rectangle under `rect`, moved by `offset`.
+pub(crate) fn draw_shadow(
+    rect: LocalRect,
+    corner_radius: f32,
+    color: Color,
+    radius: f32,
+    offset: LocalOffset,
+    args: &mut DrawArgs,
+) {
+    let paint = args.vger.color_paint(args.cx.faded(color));
+    args.vger
+        .fill_blurred_rect(rect.translate(offset), corner_radius, paint, radius);
+}
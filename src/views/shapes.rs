@@ -1,9 +1,132 @@
 use crate::*;
+use std::any::Any;
+
+/// Fill used by shapes which set neither a fill nor a stroke.
+pub(crate) const DEFAULT_FILL: Paint = Paint::Color(Color::CYAN);
+
+/// Fill, stroke and shadow shared by the shapes.
+#[derive(Clone, Default)]
+pub(crate) struct ShapeStyle {
+    pub(crate) fill: Option<Paint>,
+    pub(crate) stroke: Option<(Paint, f32)>,
+
+    /// Dash and gap lengths along the stroke.
+    pub(crate) dash: Option<(f32, f32)>,
+
+    /// Color, blur radius and offset of the shadow.
+    pub(crate) shadow: Option<(Color, f32, LocalOffset)>,
+}
+
+impl ShapeStyle {
+    /// The fill, if the shape is filled at all. A shape with only a stroke
+    /// is just outlined.
+    pub(crate) fn fill(&self) -> Option<&Paint> {
+        match (&self.fill, &self.stroke) {
+            (Some(paint), _) => Some(paint),
+            (None, None) => Some(&DEFAULT_FILL),
+            (None, Some(_)) => None,
+        }
+    }
+
+    pub(crate) fn step(&self, id: ViewId, cx: &mut Context, dt: f32) {
+        if let Some(paint) = self.fill() {
+            paint.step(id, cx, dt);
+        }
+    }
+
+    pub(crate) fn fill_paint(&self, id: ViewId, args: &mut DrawArgs) -> Option<PaintIndex> {
+        let paint = self.fill()?.animated(id, args.cx).faded(args.cx);
+        Some(paint.vger_paint(args.vger))
+    }
+
+    fn stroke_paint(&self, args: &mut DrawArgs) -> Option<(PaintIndex, f32)> {
+        let (paint, width) = self.stroke.as_ref()?;
+        Some((paint.faded(args.cx).vger_paint(args.vger), *width))
+    }
+
+    fn draw_shadow(&self, rect: LocalRect, corner_radius: f32, args: &mut DrawArgs) {
+        if let Some((color, radius, offset)) = self.shadow {
+            draw_shadow(rect, corner_radius, color, radius, offset, args);
+        }
+    }
+
+    /// Draws a filled and stroked rounded rectangle, which is also how
+    /// circles are drawn. The stroke stays inside `rect`.
+    fn draw_rounded_rect(
+        &self,
+        id: ViewId,
+        rect: LocalRect,
+        corner_radius: f32,
+        args: &mut DrawArgs,
+    ) {
+        self.draw_shadow(rect, corner_radius, args);
+
+        if let Some(paint) = self.fill_paint(id, args) {
+            args.vger.fill_rect(rect, corner_radius, paint);
+        }
+
+        if let Some((paint, width)) = self.stroke_paint(args) {
+            let inset = width / 2.0;
+            let rect = rect.inflate(-inset, -inset);
+            let corner_radius = (corner_radius - inset).max(0.0);
+            if self.dash.is_some() {
+                let points = rounded_rect_points(rect, corner_radius);
+                stroke_polyline(args.vger, &points, true, width, self.dash, paint);
+            } else {
+                args.vger
+                    .stroke_rect(rect.min(), rect.max(), corner_radius, width, paint);
+            }
+        }
+    }
+}
+
+/// Adds the fill, stroke and shadow builders to a shape with a `style`.
+macro_rules! impl_shape_style {
+    ($t:ty) => {
+        impl $t {
+            /// Sets the fill color.
+            pub fn color(self, color: Color) -> Self {
+                self.fill(color)
+            }
+
+            /// Sets the fill. It's drawn under the stroke, if there is one.
+            pub fn fill(mut self, paint: impl Into<Paint>) -> Self {
+                self.style.fill = Some(paint.into());
+                self
+            }
+
+            /// Outlines the shape. Unless a fill is also set, the shape is
+            /// only outlined.
+            pub fn stroke(mut self, paint: impl Into<Paint>, width: f32) -> Self {
+                self.style.stroke = Some((paint.into(), width));
+                self
+            }
+
+            /// Dashes the stroke, alternating dashes and gaps of the given
+            /// lengths.
+            pub fn dash(mut self, dash: f32, gap: f32) -> Self {
+                self.style.dash = Some((dash, gap));
+                self
+            }
+
+            /// Casts a shadow in the shape's outline, blurred by `radius`.
+            pub fn shadow(
+                mut self,
+                color: Color,
+                radius: f32,
+                offset: impl Into<LocalOffset>,
+            ) -> Self {
+                self.style.shadow = Some((color, radius, offset.into()));
+                self
+            }
+        }
+    };
+}
 
 /// Struct for `circle`.
 #[derive(Clone)]
 pub struct Circle {
-    paint: Paint,
+    style: ShapeStyle,
 }
 
 impl Circle {
@@ -12,21 +135,33 @@ impl Circle {
 
         (rect.center(), rect.size.width.min(rect.size.height) / 2.0)
     }
+}
+
+impl_shape_style!(Circle);
 
-    pub fn color(self, color: Color) -> Circle {
-        Circle {
-            paint: Paint::Color(color),
+impl DynView for Circle {
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        _actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim { dt, .. } = event {
+            let id = cx.view_id(path);
+            self.style.step(id, cx, *dt);
         }
     }
-}
 
-impl DynView for Circle {
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
         let (center, radius) = self.geom(path, args.cx);
+        let rect = LocalRect::new(
+            center - LocalOffset::new(radius, radius),
+            [2.0 * radius, 2.0 * radius].into(),
+        );
 
-        let vger = &mut args.vger;
-        let paint = self.paint.vger_paint(vger);
-        vger.fill_circle(center, radius, paint);
+        let id = args.cx.view_id(path);
+        self.style.draw_rounded_rect(id, rect, radius, args);
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
@@ -60,7 +195,7 @@ impl private::Sealed for Circle {}
 /// Renders a circle which expands to fill available space.
 pub fn circle() -> Circle {
     Circle {
-        paint: Paint::Color(Color::CYAN),
+        style: ShapeStyle::default(),
     }
 }
 
@@ -68,7 +203,7 @@ pub fn circle() -> Circle {
 #[derive(Clone)]
 pub struct Rectangle {
     corner_radius: f32,
-    paint: Paint,
+    style: ShapeStyle,
 }
 
 impl Rectangle {
@@ -76,30 +211,37 @@ impl Rectangle {
         cx.get_layout(path).rect
     }
 
-    /// Sets the fill color for the rectangle.
-    pub fn color(self, color: Color) -> Rectangle {
-        Rectangle {
-            corner_radius: self.corner_radius,
-            paint: Paint::Color(color),
-        }
-    }
-
     /// Sets the rectangle's corner radius.
     pub fn corner_radius(self, radius: f32) -> Rectangle {
         Rectangle {
             corner_radius: radius,
-            paint: self.paint,
+            ..self
         }
     }
 }
 
+impl_shape_style!(Rectangle);
+
 impl DynView for Rectangle {
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        _actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim { dt, .. } = event {
+            let id = cx.view_id(path);
+            self.style.step(id, cx, *dt);
+        }
+    }
+
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
         let rect = self.geom(path, args.cx);
 
-        let vger = &mut args.vger;
-        let paint = self.paint.vger_paint(vger);
-        vger.fill_rect(rect, self.corner_radius, paint);
+        let id = args.cx.view_id(path);
+        self.style
+            .draw_rounded_rect(id, rect, self.corner_radius, args);
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
@@ -134,6 +276,172 @@ impl private::Sealed for Rectangle {}
 pub fn rectangle() -> Rectangle {
     Rectangle {
         corner_radius: 0.0,
-        paint: Paint::Color(Color::CYAN),
+        style: ShapeStyle::default(),
+    }
+}
+
+/// Points around a rounded rectangle, with the corners flattened into
+/// short segments.
+pub(crate) fn rounded_rect_points(rect: LocalRect, corner_radius: f32) -> Vec<LocalPoint> {
+    const STEPS: usize = 16;
+
+    let r = corner_radius
+        .min(rect.width() / 2.0)
+        .min(rect.height() / 2.0)
+        .max(0.0);
+    let corners = [
+        (LocalPoint::new(rect.max_x() - r, rect.min_y() + r), -0.5),
+        (LocalPoint::new(rect.max_x() - r, rect.max_y() - r), 0.0),
+        (LocalPoint::new(rect.min_x() + r, rect.max_y() - r), 0.5),
+        (LocalPoint::new(rect.min_x() + r, rect.min_y() + r), 1.0),
+    ];
+
+    let mut points = vec![];
+    for (center, start) in corners {
+        if r == 0.0 {
+            points.push(center);
+            continue;
+        }
+        for i in 0..=STEPS {
+            let theta = (start + 0.5 * i as f32 / STEPS as f32) * std::f32::consts::PI;
+            points.push(center + LocalOffset::new(theta.cos(), theta.sin()) * r);
+        }
+    }
+    points
+}
+
+/// Splits a line through `points` into the runs drawn by a dashed stroke.
+/// Without a dash, the whole line is one run.
+fn dash_runs(
+    points: &[LocalPoint],
+    closed: bool,
+    dash: Option<(f32, f32)>,
+) -> Vec<Vec<LocalPoint>> {
+    let mut line = points.to_vec();
+    if closed && !points.is_empty() {
+        line.push(points[0]);
+    }
+
+    let (dash, gap) = match dash {
+        Some((dash, gap)) if dash > 0.0 && gap > 0.0 => (dash, gap),
+        _ => return vec![line],
+    };
+
+    let mut runs = vec![];
+    let mut run: Vec<LocalPoint> = line.iter().take(1).copied().collect();
+    let mut on = true;
+    let mut phase = 0.0;
+
+    for segment in line.windows(2) {
+        let (a, b) = (segment[0], segment[1]);
+        let len = a.distance_to(b);
+        let mut t = 0.0;
+        while t < len {
+            let left = if on { dash } else { gap } - phase;
+            if left > len - t {
+                phase += len - t;
+                break;
+            }
+            t += left;
+            let p = a.lerp(b, t / len);
+            if on {
+                run.push(p);
+                runs.push(std::mem::take(&mut run));
+            } else {
+                run = vec![p];
+            }
+            phase = 0.0;
+            on = !on;
+        }
+        if on && run.last() != Some(&b) {
+            run.push(b);
+        }
+    }
+
+    if on && run.len() > 1 {
+        // A dash running through the start of a closed line is one dash.
+        if closed && !runs.is_empty() {
+            run.extend_from_slice(&runs[0][1..]);
+            runs[0] = run;
+        } else {
+            runs.push(run);
+        }
+    }
+    runs
+}
+
+/// Strokes a line through `points` with round joins, optionally dashed.
+pub(crate) fn stroke_polyline(
+    vger: &mut Vger,
+    points: &[LocalPoint],
+    closed: bool,
+    width: f32,
+    dash: Option<(f32, f32)>,
+    paint: PaintIndex,
+) {
+    for run in dash_runs(points, closed, dash) {
+        if run.len() < 2 {
+            continue;
+        }
+        // A run which closes on itself has a join at its start too.
+        let joins = if run.first() == run.last() {
+            &run[1..]
+        } else {
+            &run[1..run.len() - 1]
+        };
+        for p in joins {
+            vger.fill_circle(*p, width / 2.0, paint);
+        }
+        for segment in run.windows(2) {
+            if segment[0] != segment[1] {
+                vger.stroke_segment(segment[0], segment[1], width, paint);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_fill_and_stroke() {
+        // Shapes are filled until they're given a stroke.
+        assert!(circle().style.fill().is_some());
+        assert!(rectangle().stroke(BLACK, 1.0).style.fill().is_none());
+        assert!(rectangle()
+            .stroke(BLACK, 1.0)
+            .fill(BLACK)
+            .style
+            .fill()
+            .is_some());
+    }
+
+    #[test]
+    fn test_dash_runs() {
+        let square =
+            rounded_rect_points(LocalRect::new(LocalPoint::zero(), [10.0, 10.0].into()), 0.0);
+        assert_eq!(square.len(), 4);
+
+        // Solid strokes are one run, closed back to the start.
+        let runs = dash_runs(&square, true, None);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].len(), 5);
+
+        // Dashes turn corners, and the last one joins the first.
+        let runs = dash_runs(&square, true, Some((15.0, 5.0)));
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].len(), 3);
+        let length: f32 = runs
+            .iter()
+            .flat_map(|run| run.windows(2).map(|s| s[0].distance_to(s[1])))
+            .sum();
+        assert!((length - 30.0).abs() < 1e-4);
+
+        // An open line ends wherever the pattern is.
+        let runs = dash_runs(&square[..2], false, Some((4.0, 2.0)));
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1], vec![[10.0, 6.0].into(), [10.0, 10.0].into()]);
     }
 }
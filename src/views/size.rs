@@ -22,6 +22,10 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
+        if let Event::Anim { dt, .. } = event {
+            let id = cx.view_id(path);
+            cx.step_animated(id, self.size, *dt);
+        }
         path.push(0);
         self.child.process(event, path, cx, actions);
         path.pop();
@@ -34,10 +38,12 @@ where
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let id = args.cx.view_id(path);
+        let size = args.cx.animated(id, self.size);
         path.push(0);
-        self.child.layout(path, &mut args.size(self.size));
+        self.child.layout(path, &mut args.size(size));
         path.pop();
-        self.size
+        size
     }
 
     fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
@@ -59,7 +65,20 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
         path.push(0);
         self.child.gc(path, cx, map);
         path.pop();
@@ -1,74 +1,86 @@
 use crate::*;
 
-const SLIDER_WIDTH: f32 = 4.0;
-const SLIDER_THUMB_RADIUS: f32 = 10.0;
+pub(crate) const SLIDER_WIDTH: f32 = 4.0;
+pub(crate) const SLIDER_THUMB_RADIUS: f32 = 10.0;
 
-#[derive(Clone, Copy)]
-pub struct SliderOptions {
-    thumb: Color,
-}
+/// Change in value for each arrow key press.
+const SLIDER_KEY_STEP: f32 = 0.05;
 
-impl Default for SliderOptions {
-    fn default() -> Self {
-        Self {
-            thumb: AZURE_HIGHLIGHT,
-        }
-    }
+#[derive(Clone, Copy, Default)]
+pub struct SliderOptions {
+    /// The theme's accent color unless set.
+    thumb: Option<Color>,
 }
 
 pub trait SliderMods: View + Sized {
     fn thumb_color(self, color: Color) -> Self;
 }
 
-/// Horizontal slider built from other Views.
-pub fn hslider(value: impl Binding<f32>) -> impl SliderMods {
-    modview(move |opts: SliderOptions, _| {
-        state(
-            || 0.0,
-            move |width, cx| {
-                let w = cx[width];
-                canvas(move |cx, sz, vger| {
-                    let c = sz.center();
+#[derive(Default)]
+struct SliderState {
+    /// Width, or height for a vertical slider.
+    length: f32,
+    hovered: bool,
+    dragging: bool,
+}
 
-                    let w = cx[width];
-                    let v = value.get(cx);
-                    let r = SLIDER_THUMB_RADIUS;
-                    let start_x = r;
-                    let end_x = w - r;
-                    let x = (1.0 - v) * start_x + v * (end_x);
+/// Step for an arrow key, if it moves the slider.
+fn key_step(key: Key) -> Option<f32> {
+    match key {
+        Key::ArrowUp | Key::ArrowRight => Some(SLIDER_KEY_STEP),
+        Key::ArrowDown | Key::ArrowLeft => Some(-SLIDER_KEY_STEP),
+        _ => None,
+    }
+}
 
-                    let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
-                    vger.fill_rect(
-                        euclid::rect(
-                            start_x,
-                            c.y - SLIDER_WIDTH / 2.0,
-                            sz.size.width - 2.0 * r,
-                            SLIDER_WIDTH,
-                        ),
-                        0.0,
-                        paint,
-                    );
-                    let paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
-                    vger.fill_rect(
-                        euclid::rect(start_x, c.y - SLIDER_WIDTH / 2.0, x, SLIDER_WIDTH),
-                        0.0,
-                        paint,
-                    );
-                    let paint = vger.color_paint(opts.thumb);
-                    vger.fill_circle([x, c.y], r, paint);
-                })
+/// A slider drawn by the `SliderStyle` from the environment, which handles
+/// hovering, dragging and arrow keys.
+fn slider(value: impl Binding<f32>, vertical: bool, opts: SliderOptions) -> impl View {
+    keyboard_focus(move |has_focus| {
+        state(SliderState::default, move |s, cx| {
+            let config = SliderConfiguration {
+                value: *value.get(cx),
+                vertical,
+                thumb: opts.thumb,
+                state: ControlState {
+                    hovered: cx[s].hovered,
+                    pressed: cx[s].dragging,
+                    focused: has_focus,
+                    disabled: cx.is_disabled(),
+                },
+            };
+            cx.get_env::<AnySliderStyle>()
+                .body(config, &cx.theme())
                 .geom(move |cx, sz, _| {
-                    if sz.width != cx[width] {
-                        cx[width] = sz.width;
+                    let length = if vertical { sz.height } else { sz.width };
+                    if length != cx[s].length {
+                        cx[s].length = length;
                     }
                 })
-                .drag_s(value, move |v, delta, _, _| {
-                    *v = (*v + delta.x / w).clamp(0.0, 1.0)
+                .drag(move |cx, delta, gesture, _| {
+                    cx[s].dragging = gesture != GestureState::Ended;
+                    let length = cx[s].length;
+                    let delta = if vertical { delta.y } else { delta.x };
+                    if length > 0.0 {
+                        value.with_mut(cx, |v| *v = (*v + delta / length).clamp(0.0, 1.0));
+                    }
                 })
-            },
-        )
-        .role(accesskit::Role::Slider)
+                .hover(move |cx, inside| cx[s].hovered = inside)
+                .key(move |cx, key| {
+                    if let (true, Some(step)) = (has_focus, key_step(key)) {
+                        value.with_mut(cx, |v| *v = (*v + step).clamp(0.0, 1.0));
+                    }
+                })
+        })
     })
+    .role(accesskit::Role::Slider)
+}
+
+/// Horizontal slider for a value from 0 to 1.
+///
+/// Its look comes from the `SliderStyle` set with `slider_style`.
+pub fn hslider(value: impl Binding<f32>) -> impl SliderMods {
+    modview(move |opts: SliderOptions, _| slider(value, false, opts))
 }
 
 impl<F> SliderMods for ModView<SliderOptions, F>
@@ -77,7 +89,7 @@ where
 {
     fn thumb_color(self, color: Color) -> Self {
         let mut opts = self.value;
-        opts.thumb = color;
+        opts.thumb = Some(color);
         ModView {
             func: self.func,
             value: opts,
@@ -85,37 +97,48 @@ where
     }
 }
 
-/// Vertical slider built from other Views.
+/// Vertical slider for a value from 0 (bottom) to 1.
 pub fn vslider(
     value: f32,
     set_value: impl Fn(&mut Context, f32) + 'static + Copy,
 ) -> impl SliderMods {
     modview(move |opts: SliderOptions, _| {
-        state(
-            || 0.0,
-            move |height, _| {
-                canvas(move |cx, sz, vger| {
-                    let h = cx[height];
-                    let y = value * h;
-                    let c = sz.center();
-                    let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
-                    vger.fill_rect(
-                        euclid::rect(c.x - SLIDER_WIDTH / 2.0, 0.0, SLIDER_WIDTH, sz.height()),
-                        0.0,
-                        paint,
-                    );
-                    let paint = vger.color_paint(opts.thumb);
-                    vger.fill_circle([c.x, y], SLIDER_THUMB_RADIUS, paint);
-                })
-                .geom(move |cx, sz, _| {
-                    if sz.height != cx[height] {
-                        cx[height] = sz.height;
-                    }
-                })
-                .drag(move |cx, delta, _, _| {
-                    (set_value)(cx, (value + delta.y / cx[height]).clamp(0.0, 1.0));
-                })
-            },
+        map(
+            value,
+            move |v, cx| set_value(cx, v),
+            move |v, _| slider(v, true, opts),
         )
     })
 }
+
+/// The built-in look of sliders: a groove, filled up to a round thumb.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultSliderStyle;
+
+impl SliderStyle for DefaultSliderStyle {
+    fn body(&self, config: SliderConfiguration, theme: &Theme) -> AnyView {
+        let theme = *theme;
+        any_view(canvas(move |_, rect, vger| {
+            let c = rect.center();
+            let r = SLIDER_THUMB_RADIUS;
+            let v = config.value;
+            let thumb = vger.color_paint(config.thumb.unwrap_or(theme.accent));
+            let track = vger.color_paint(theme.track);
+
+            if config.vertical {
+                let y = v * rect.height();
+                let groove =
+                    euclid::rect(c.x - SLIDER_WIDTH / 2.0, 0.0, SLIDER_WIDTH, rect.height());
+                vger.fill_rect(groove, 0.0, track);
+                vger.fill_circle([c.x, y], r, thumb);
+            } else {
+                let x = (1.0 - v) * r + v * (rect.width() - r);
+                let groove = |w| euclid::rect(r, c.y - SLIDER_WIDTH / 2.0, w, SLIDER_WIDTH);
+                vger.fill_rect(groove(rect.width() - 2.0 * r), 0.0, track);
+                let fill = vger.color_paint(theme.accent_background);
+                vger.fill_rect(groove(x), 0.0, fill);
+                vger.fill_circle([x, c.y], r, thumb);
+            }
+        }))
+    }
+}
@@ -252,6 +252,23 @@ impl<VT: ViewTuple + 'static, D: StackDirection + 'static> DynView for Stack<VT,
         });
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let mut c = 0;
+        self.children.foreach_view(&mut |child| {
+            path.push(c);
+            let offset = cx.get_layout(path).offset;
+            child.focusables(path, xform.pre_translate(offset), cx, ids);
+            path.pop();
+            c += 1;
+        });
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         map.push(cx.view_id(path));
         let mut c = 0;
@@ -337,16 +354,16 @@ impl<VT: ViewTuple, D: StackDirection> Stack<VT, D> {
 impl<VT, D> private::Sealed for Stack<VT, D> {}
 
 /// Horizontal stack of up to 128 Views in a tuple. Each item can be a different view type.
-pub fn hstack<VT: ViewTuple + 'static>(children: VT) -> Stack::<VT, HorizontalDirection> {
+pub fn hstack<VT: ViewTuple + 'static>(children: VT) -> Stack<VT, HorizontalDirection> {
     Stack::<VT, HorizontalDirection>::new(children)
 }
 
 /// Vertical stack of up to 128 Views in a tuple. Each item can be a different view type.
-pub fn vstack<VT: ViewTuple + 'static>(children: VT) -> Stack::<VT, VerticalDirection> {
+pub fn vstack<VT: ViewTuple + 'static>(children: VT) -> Stack<VT, VerticalDirection> {
     Stack::<VT, VerticalDirection>::new(children)
 }
 
 /// Stack of up to 128 overlaid Views in a tuple. Each item can be a different view type.
-pub fn zstack<VT: ViewTuple + 'static>(children: VT) -> Stack::<VT, ZDirection> {
+pub fn zstack<VT: ViewTuple + 'static>(children: VT) -> Stack<VT, ZDirection> {
     Stack::<VT, ZDirection>::new(children)
 }
@@ -169,6 +169,20 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let id = cx.view_id(path);
+        cx.init_state(id, &self.default);
+        path.push(0);
+        (self.func)(StateHandle::new(id), cx).focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         let id = cx.view_id(path);
         cx.init_state(id, &self.default);
@@ -223,6 +237,9 @@ pub fn with_cx<V: View, F: Fn(&Context) -> V + Clone + 'static>(f: F) -> impl Vi
 }
 
 /// Convenience to retreive a reference to a value in the context.
-pub fn with_ref<V: View, F: Fn(&T) -> V + Clone + 'static, T>(binding: impl Binding<T>, f: F) -> impl View {
+pub fn with_ref<V: View, F: Fn(&T) -> V + Clone + 'static, T>(
+    binding: impl Binding<T>,
+    f: F,
+) -> impl View {
     with_cx(move |cx| f(binding.get(cx)))
 }
@@ -0,0 +1,158 @@
+use crate::*;
+use std::sync::Arc;
+
+/// Interaction state of a control, passed to its style.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ControlState {
+    /// The mouse is over the control.
+    pub hovered: bool,
+
+    /// The control is being pressed or dragged.
+    pub pressed: bool,
+
+    /// The control has the keyboard focus.
+    pub focused: bool,
+
+    /// The control doesn't respond to input.
+    pub disabled: bool,
+}
+
+/// What a `ButtonStyle` draws.
+#[derive(Clone)]
+pub struct ButtonConfiguration {
+    pub label: AnyView,
+    pub state: ControlState,
+}
+
+/// What a `ToggleStyle` draws.
+#[derive(Clone, Copy, Debug)]
+pub struct ToggleConfiguration {
+    pub on: bool,
+
+    /// Goes from 0 (off) to 1 (on), following `on` with an animation.
+    pub position: f32,
+    pub state: ControlState,
+}
+
+/// What a `SliderStyle` draws.
+#[derive(Clone, Copy, Debug)]
+pub struct SliderConfiguration {
+    /// From 0 to 1.
+    pub value: f32,
+
+    /// For `vslider`, where 0 is at the bottom.
+    pub vertical: bool,
+
+    /// Color set with `thumb_color`, if any.
+    pub thumb: Option<Color>,
+    pub state: ControlState,
+}
+
+/// Draws buttons. The button itself handles input, so styles only need to
+/// return the view for each state.
+///
+/// Set it for a subtree with the `button_style` modifier.
+pub trait ButtonStyle: 'static {
+    fn body(&self, config: ButtonConfiguration, theme: &Theme) -> AnyView;
+}
+
+/// Draws toggle switches. Set it for a subtree with the `toggle_style` modifier.
+pub trait ToggleStyle: 'static {
+    fn body(&self, config: ToggleConfiguration, theme: &Theme) -> AnyView;
+}
+
+/// Draws sliders, filling the space offered. Set it for a subtree with
+/// the `slider_style` modifier.
+pub trait SliderStyle: 'static {
+    fn body(&self, config: SliderConfiguration, theme: &Theme) -> AnyView;
+}
+
+/// Style set with `button_style`.
+#[derive(Clone)]
+pub struct AnyButtonStyle(pub(crate) Arc<dyn ButtonStyle>);
+
+impl Default for AnyButtonStyle {
+    fn default() -> Self {
+        Self(Arc::new(DefaultButtonStyle))
+    }
+}
+
+impl ButtonStyle for AnyButtonStyle {
+    fn body(&self, config: ButtonConfiguration, theme: &Theme) -> AnyView {
+        self.0.body(config, theme)
+    }
+}
+
+/// Style set with `toggle_style`.
+#[derive(Clone)]
+pub struct AnyToggleStyle(pub(crate) Arc<dyn ToggleStyle>);
+
+impl Default for AnyToggleStyle {
+    fn default() -> Self {
+        Self(Arc::new(ToggleConfig::default()))
+    }
+}
+
+impl ToggleStyle for AnyToggleStyle {
+    fn body(&self, config: ToggleConfiguration, theme: &Theme) -> AnyView {
+        self.0.body(config, theme)
+    }
+}
+
+/// Style set with `slider_style`.
+#[derive(Clone)]
+pub struct AnySliderStyle(pub(crate) Arc<dyn SliderStyle>);
+
+impl Default for AnySliderStyle {
+    fn default() -> Self {
+        Self(Arc::new(DefaultSliderStyle))
+    }
+}
+
+impl SliderStyle for AnySliderStyle {
+    fn body(&self, config: SliderConfiguration, theme: &Theme) -> AnyView {
+        self.0.body(config, theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    struct SquareButtonStyle;
+
+    impl ButtonStyle for SquareButtonStyle {
+        fn body(&self, config: ButtonConfiguration, _theme: &Theme) -> AnyView {
+            let size = if config.state.pressed { 50.0 } else { 40.0 };
+            any_view(rectangle().size([size, size]))
+        }
+    }
+
+    #[test]
+    fn test_button_style() {
+        let mut cx = Context::new();
+        let ui = button("styled", |_| ()).button_style(SquareButtonStyle);
+
+        let layout = |cx: &mut Context| {
+            let mut path = vec![0];
+            ui.layout(
+                &mut path,
+                &mut LayoutArgs {
+                    sz: [100.0, 100.0].into(),
+                    cx,
+                    text_bounds: &mut |_, _, _| LocalRect::zero(),
+                },
+            )
+        };
+        assert_eq!(layout(&mut cx), [40.0, 40.0].into());
+
+        let mut actions = vec![];
+        let begin = Event::TouchBegin {
+            id: 0,
+            position: [20.0, 20.0].into(),
+        };
+        ui.process(&begin, &mut vec![0], &mut cx, &mut actions);
+        assert_eq!(layout(&mut cx), [50.0, 50.0].into());
+    }
+}
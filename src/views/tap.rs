@@ -99,17 +99,26 @@ where
             Event::TouchEnd { id, position } => {
                 if cx.touches[*id] == vid {
                     cx.touches[*id] = ViewId::default();
-                    self.func.call(
-                        cx,
-                        TapInfo {
-                            pt: *position,
-                            button: cx.mouse_button,
-                            state: TouchState::End,
-                        },
-                        actions,
-                    )
+                    // Views disabled mid-tap release the touch without tapping.
+                    if !cx.is_disabled() {
+                        self.func.call(
+                            cx,
+                            TapInfo {
+                                pt: *position,
+                                button: cx.mouse_button,
+                                state: TouchState::End,
+                            },
+                            actions,
+                        )
+                    }
                 }
             }
+            Event::Anim { .. } => {
+                // Animations inside the gesture still run.
+                path.push(0);
+                self.child.process(event, path, cx, actions);
+                path.pop();
+            }
             _ => (),
         }
     }
@@ -140,6 +149,18 @@ where
         path.pop();
     }
 
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         path.push(0);
         self.child.gc(path, cx, map);
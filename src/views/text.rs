@@ -4,6 +4,31 @@ pub trait TextModifiers: View + Sized {
     fn font_size(self, size: u32) -> Text;
     fn color(self, color: Color) -> Text;
     fn max_width(self, max_width: f32) -> Text;
+
+    /// Wraps lines to fit the width offered. On by default.
+    fn wrap(self, wrap: bool) -> Text;
+
+    /// Limits the number of lines, truncating the text beyond.
+    fn line_limit(self, lines: usize) -> Text;
+
+    /// Where to cut text which doesn't fit its line limit.
+    fn truncation(self, truncation: Truncation) -> Text;
+    fn alignment(self, alignment: TextAlignment) -> Text;
+
+    /// Height of lines, relative to the font size.
+    fn line_height(self, line_height: f32) -> Text;
+
+    /// Extra space between characters.
+    fn letter_spacing(self, letter_spacing: f32) -> Text;
+
+    /// Sets the whole font, rather than inheriting parts of it from the environment.
+    fn font(self, font: Font) -> Text;
+    fn font_family(self, family: impl Into<FontFamily>) -> Text;
+    fn font_weight(self, weight: FontWeight) -> Text;
+    fn italic(self) -> Text;
+
+    /// Uses the monospace family, so digits line up.
+    fn monospaced(self) -> Text;
 }
 
 /// Struct for `text`.
@@ -11,41 +36,62 @@ pub trait TextModifiers: View + Sized {
 pub struct Text {
     text: String,
     size: u32,
-    color: Color,
+
+    /// The theme's text color unless set.
+    color: Option<Color>,
     max_width: Option<f32>,
+    style: ParagraphStyle,
+    font: FontAttributes,
 }
 
 impl Text {
     pub const DEFAULT_SIZE: u32 = 18;
     pub fn color(self, color: Color) -> Text {
         Text {
-            text: self.text,
-            size: self.size,
-            color,
-            max_width: self.max_width,
+            color: Some(color),
+            ..self
         }
     }
 }
 
 impl DynView for Text {
-    fn draw(&self, _path: &mut IdPath, args: &mut DrawArgs) {
-        let vger = &mut args.vger;
-        let origin = vger
-            .text_bounds(self.text.as_str(), self.size, self.max_width)
-            .origin;
-
-        vger.save();
-        vger.translate([-origin.x, -origin.y]);
-        vger.text(self.text.as_str(), self.size, self.color, self.max_width);
-        vger.restore();
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let id = args.cx.view_id(path);
+        if let Some(paragraph) = stored_paragraph(id, args.cx) {
+            draw_paragraph(paragraph, args.cx, args.vger);
+        }
     }
-    fn layout(&self, _path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
-        (args.text_bounds)(self.text.as_str(), self.size, None).size
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let mut span = span(&self.text).font_size(self.size);
+        span.color = self.color;
+        span.font = self.font.clone();
+        let max_width = match self.max_width {
+            Some(w) => w.min(args.sz.width),
+            None => args.sz.width,
+        };
+        let font = args.cx.init_env(&Font::default);
+        let color = args.cx.theme().text;
+        let paragraph = layout_paragraph(
+            &[span],
+            &font,
+            color,
+            max_width,
+            &self.style,
+            args.text_bounds,
+        );
+        let size = paragraph.size;
+        let id = args.cx.view_id(path);
+        args.cx.set_state(id, paragraph);
+        size
     }
     fn hittest(&self, _path: &mut IdPath, _pt: LocalPoint, _cx: &mut Context) -> Option<ViewId> {
         None
     }
 
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+    }
+
     fn access(
         &self,
         path: &mut IdPath,
@@ -62,59 +108,94 @@ impl DynView for Text {
 
 impl TextModifiers for Text {
     fn font_size(self, size: u32) -> Self {
-        Self {
-            text: self.text,
-            color: self.color,
-            size,
-            max_width: self.max_width,
-        }
+        Self { size, ..self }
     }
     fn color(self, color: Color) -> Text {
         Text {
-            text: self.text,
-            size: self.size,
-            color,
-            max_width: self.max_width,
+            color: Some(color),
+            ..self
         }
     }
     fn max_width(self, max_width: f32) -> Text {
         Text {
-            text: self.text,
-            size: self.size,
-            color: self.color,
             max_width: Some(max_width),
+            ..self
         }
     }
+    fn wrap(mut self, wrap: bool) -> Text {
+        self.style.wrap = wrap;
+        self
+    }
+    fn line_limit(mut self, lines: usize) -> Text {
+        self.style.line_limit = Some(lines);
+        self
+    }
+    fn truncation(mut self, truncation: Truncation) -> Text {
+        self.style.truncation = truncation;
+        self
+    }
+    fn alignment(mut self, alignment: TextAlignment) -> Text {
+        self.style.alignment = alignment;
+        self
+    }
+    fn line_height(mut self, line_height: f32) -> Text {
+        self.style.line_height = line_height;
+        self
+    }
+    fn letter_spacing(mut self, letter_spacing: f32) -> Text {
+        self.style.letter_spacing = letter_spacing;
+        self
+    }
+    fn font(mut self, font: Font) -> Text {
+        self.font.set(font);
+        self
+    }
+    fn font_family(mut self, family: impl Into<FontFamily>) -> Text {
+        self.font.family = Some(family.into());
+        self
+    }
+    fn font_weight(mut self, weight: FontWeight) -> Text {
+        self.font.weight = Some(weight);
+        self
+    }
+    fn italic(mut self) -> Text {
+        self.font.italic = Some(true);
+        self
+    }
+    fn monospaced(self) -> Text {
+        self.font_family(FontFamily::Monospace)
+    }
 }
 
 impl private::Sealed for Text {}
 
 /// Shows a string as a label (not editable).
+///
+/// Lines wrap to fit the width offered.
 pub fn text(name: &str) -> Text {
     Text {
         text: String::from(name),
         size: Text::DEFAULT_SIZE,
-        color: TEXT_COLOR,
+        color: None,
         max_width: None,
+        style: ParagraphStyle::default(),
+        font: FontAttributes::default(),
     }
 }
 
-macro_rules! impl_text {
-    ( $ty:ident ) => {
+/// Implements the text traits for a type shown with its `Display` formatting,
+/// by forwarding to `Text`.
+macro_rules! impl_display_text {
+    ( $ty:ty ) => {
         impl DynView for $ty {
-            fn draw(&self, _path: &mut IdPath, args: &mut DrawArgs) {
-                let txt = &format!("{}", self);
-                let vger = &mut args.vger;
-                let origin = vger.text_bounds(txt, Text::DEFAULT_SIZE, None).origin;
-
-                vger.save();
-                vger.translate([-origin.x, -origin.y]);
-                vger.text(txt, Text::DEFAULT_SIZE, TEXT_COLOR, None);
-                vger.restore();
+            fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+                text(&format!("{}", self)).draw(path, args)
+            }
+            fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+                text(&format!("{}", self)).layout(path, args)
             }
-            fn layout(&self, _path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
-                let txt = &format!("{}", self);
-                (args.text_bounds)(txt, Text::DEFAULT_SIZE, None).size
+            fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+                map.push(cx.view_id(path));
             }
 
             fn access(
@@ -123,39 +204,52 @@ macro_rules! impl_text {
                 cx: &mut Context,
                 nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
             ) -> Option<accesskit::NodeId> {
-                let aid = cx.view_id(path).access_id();
-                let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Label);
-                builder.set_name(format!("{}", self));
-                nodes.push((aid, builder.build()));
-                Some(aid)
+                text(&format!("{}", self)).access(path, cx, nodes)
             }
         }
 
         impl TextModifiers for $ty {
             fn font_size(self, size: u32) -> Text {
-                Text {
-                    text: format!("{}", self),
-                    size,
-                    color: TEXT_COLOR,
-                    max_width: None,
-                }
+                text(&format!("{}", self)).font_size(size)
             }
             fn color(self, color: Color) -> Text {
-                Text {
-                    text: format!("{}", self),
-                    size: Text::DEFAULT_SIZE,
-                    color,
-                    max_width: None,
-                }
+                TextModifiers::color(text(&format!("{}", self)), color)
             }
-
             fn max_width(self, max_width: f32) -> Text {
-                Text {
-                    text: format!("{}", self),
-                    size: Text::DEFAULT_SIZE,
-                    color: TEXT_COLOR,
-                    max_width: Some(max_width),
-                }
+                text(&format!("{}", self)).max_width(max_width)
+            }
+            fn wrap(self, wrap: bool) -> Text {
+                text(&format!("{}", self)).wrap(wrap)
+            }
+            fn line_limit(self, lines: usize) -> Text {
+                text(&format!("{}", self)).line_limit(lines)
+            }
+            fn truncation(self, truncation: Truncation) -> Text {
+                text(&format!("{}", self)).truncation(truncation)
+            }
+            fn alignment(self, alignment: TextAlignment) -> Text {
+                text(&format!("{}", self)).alignment(alignment)
+            }
+            fn line_height(self, line_height: f32) -> Text {
+                text(&format!("{}", self)).line_height(line_height)
+            }
+            fn letter_spacing(self, letter_spacing: f32) -> Text {
+                text(&format!("{}", self)).letter_spacing(letter_spacing)
+            }
+            fn font(self, font: Font) -> Text {
+                text(&format!("{}", self)).font(font)
+            }
+            fn font_family(self, family: impl Into<FontFamily>) -> Text {
+                text(&format!("{}", self)).font_family(family)
+            }
+            fn font_weight(self, weight: FontWeight) -> Text {
+                text(&format!("{}", self)).font_weight(weight)
+            }
+            fn italic(self) -> Text {
+                text(&format!("{}", self)).italic()
+            }
+            fn monospaced(self) -> Text {
+                text(&format!("{}", self)).monospaced()
             }
         }
     };
@@ -164,70 +258,13 @@ macro_rules! impl_text {
 // XXX: this used to be generic for any Display but
 //      that was causing trouble with adding Clone to view.
 //      Perhaps a rust wizard can figure out why.
-impl_text!(String);
-impl_text!(u32);
-impl_text!(i32);
-impl_text!(u64);
-impl_text!(i64);
-impl_text!(f32);
-impl_text!(f64);
-
-// XXX: Can't do impl_text!(&'static str)
-impl DynView for &'static str {
-    fn draw(&self, _path: &mut IdPath, args: &mut DrawArgs) {
-        let txt = &format!("{}", self);
-        let vger = &mut args.vger;
-        let origin = vger.text_bounds(txt, Text::DEFAULT_SIZE, None).origin;
-
-        vger.save();
-        vger.translate([-origin.x, -origin.y]);
-        vger.text(txt, Text::DEFAULT_SIZE, TEXT_COLOR, None);
-        vger.restore();
-    }
-    fn layout(&self, _path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
-        let txt = &format!("{}", self);
-        (args.text_bounds)(txt, Text::DEFAULT_SIZE, None).size
-    }
-
-    fn access(
-        &self,
-        path: &mut IdPath,
-        cx: &mut Context,
-        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
-    ) -> Option<accesskit::NodeId> {
-        let aid = cx.view_id(path).access_id();
-        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Label);
-        builder.set_name(format!("{}", self));
-        nodes.push((aid, builder.build()));
-        Some(aid)
-    }
-}
-
-impl TextModifiers for &'static str {
-    fn font_size(self, size: u32) -> Text {
-        Text {
-            text: format!("{}", self),
-            size,
-            color: TEXT_COLOR,
-            max_width: None,
-        }
-    }
-    fn color(self, color: Color) -> Text {
-        Text {
-            text: format!("{}", self),
-            size: Text::DEFAULT_SIZE,
-            color,
-            max_width: None,
-        }
-    }
-    fn max_width(self, max_width: f32) -> Text {
-        Text {
-            text: format!("{}", self),
-            size: Text::DEFAULT_SIZE,
-            color: TEXT_COLOR,
-            max_width: Some(max_width),
-        }
-    }
-}
+impl_display_text!(String);
+impl_display_text!(u32);
+impl_display_text!(i32);
+impl_display_text!(u64);
+impl_display_text!(i64);
+impl_display_text!(f32);
+impl_display_text!(f64);
+impl_display_text!(&'static str);
 
 impl<V> private::Sealed for V where V: std::fmt::Display {}
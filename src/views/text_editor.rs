@@ -1,29 +1,258 @@
 use crate::*;
+use std::ops::Range;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
-/// View-model for `text_editor`.
-struct TextEditorState {
-    cursor: usize,
-    glyph_rects: Vec<LocalRect>,
-    lines: Vec<LineMetrics>,
+/// Text and selection, restored by undo.
+#[derive(Clone)]
+struct Snapshot {
+    text: String,
+    pub(crate) cursor: usize,
+    pub(crate) anchor: usize,
+}
+
+/// Consecutive edits of the same kind undo together.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum EditKind {
+    Typing,
+    Deleting,
+    Other,
+}
+
+/// View-model for `text_editor` and `text_field`.
+#[derive(Clone)]
+pub(crate) struct TextEditorState {
+    /// Cursor position, as a byte offset on a grapheme boundary.
+    pub(crate) cursor: usize,
+
+    /// The other end of the selection. Equal to `cursor` when nothing is selected.
+    anchor: usize,
+
+    pub(crate) glyph_rects: Vec<LocalRect>,
+    pub(crate) lines: Vec<LineMetrics>,
+
+    /// Where glyph positions are measured from, in the editor's local space.
+    pub(crate) origin: LocalOffset,
+
+    /// Text being composed by the IME, shown at the cursor.
+    pub(crate) preedit: String,
+
+    /// Composition cursor, in characters within `preedit`.
+    pub(crate) preedit_cursor: Option<usize>,
+
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+
+    /// Kind of the previous edit, if nothing happened since which
+    /// should stop the next edit from coalescing with it.
+    pub(crate) last_edit: Option<EditKind>,
+}
+
+/// Byte offset of the character at index `i`, or the end of the text.
+pub(crate) fn byte_offset(text: &str, i: usize) -> usize {
+    text.char_indices().nth(i).map_or(text.len(), |(b, _)| b)
+}
+
+/// Index of the glyph at byte offset `i`. vger lays out one glyph per character.
+pub(crate) fn glyph_index(text: &str, i: usize) -> usize {
+    text[..i].chars().count()
+}
+
+/// The grapheme boundary before byte offset `i`.
+fn prev_grapheme(text: &str, i: usize) -> usize {
+    GraphemeCursor::new(i, text.len(), true)
+        .prev_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(0)
+}
+
+/// The grapheme boundary after byte offset `i`.
+fn next_grapheme(text: &str, i: usize) -> usize {
+    GraphemeCursor::new(i, text.len(), true)
+        .next_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(text.len())
+}
+
+fn is_grapheme_boundary(text: &str, i: usize) -> bool {
+    GraphemeCursor::new(i, text.len(), true)
+        .is_boundary(text, 0)
+        .unwrap_or(true)
+}
+
+/// Moves byte offset `i` back to the nearest grapheme boundary.
+pub(crate) fn snap(text: &str, i: usize) -> usize {
+    let mut i = i.min(text.len());
+    while !text.is_char_boundary(i) {
+        i -= 1;
+    }
+    if is_grapheme_boundary(text, i) {
+        i
+    } else {
+        prev_grapheme(text, i)
+    }
+}
+
+/// The end of the word after byte offset `i`.
+///
+/// Word bounds can fall inside a grapheme, for example after a prepended
+/// mark, so they are moved to the end of it.
+fn next_word(text: &str, i: usize) -> usize {
+    text.unicode_word_indices()
+        .map(|(b, w)| b + w.len())
+        .map(|end| {
+            if is_grapheme_boundary(text, end) {
+                end
+            } else {
+                next_grapheme(text, end)
+            }
+        })
+        .find(|&end| end > i)
+        .unwrap_or(text.len())
+}
+
+/// The start of the word before byte offset `i`.
+fn prev_word(text: &str, i: usize) -> usize {
+    text.unicode_word_indices()
+        .map(|(b, _)| snap(text, b))
+        .take_while(|&start| start < i)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Byte range of the word (or run of spaces, or punctuation) at byte offset `i`.
+pub(crate) fn word_range(text: &str, i: usize) -> Range<usize> {
+    let mut last = 0..0;
+    for (b, w) in text.split_word_bound_indices() {
+        last = b..b + w.len();
+        if i < last.end {
+            break;
+        }
+    }
+    last
+}
+
+/// Byte range of the line around byte offset `i`, including its newline.
+pub(crate) fn line_range(text: &str, i: usize) -> Range<usize> {
+    let start = text[..i].rfind('\n').map_or(0, |n| n + 1);
+    let end = text[i..].find('\n').map_or(text.len(), |n| i + n + 1);
+    start..end
+}
+
+/// Byte offset of the end of the line around `i`, before its newline.
+fn line_end(text: &str, i: usize) -> usize {
+    text[i..].find('\n').map_or(text.len(), |n| i + n)
 }
 
 impl TextEditorState {
-    fn fwd(&mut self, len: usize) {
-        self.cursor += 1;
-        if self.cursor > len {
-            self.cursor = len;
+    pub(crate) fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    pub(crate) fn has_selection(&self) -> bool {
+        self.cursor != self.anchor
+    }
+
+    pub(crate) fn select(&mut self, range: Range<usize>) {
+        self.anchor = range.start;
+        self.cursor = range.end;
+    }
+
+    /// Collapses the selection to the cursor, unless extending it.
+    pub(crate) fn collapse(&mut self, extend: bool) {
+        if !extend {
+            self.anchor = self.cursor;
         }
     }
-    fn back(&mut self) {
-        if self.cursor > 0 {
-            self.cursor -= 1;
+
+    /// Finishes moving the cursor. The next edit won't coalesce with the previous one.
+    fn moved(&mut self, extend: bool) {
+        self.collapse(extend);
+        self.last_edit = None;
+    }
+
+    /// Keeps the cursor and anchor valid if the text was changed elsewhere.
+    pub(crate) fn validate(&mut self, text: &str) {
+        self.cursor = snap(text, self.cursor);
+        self.anchor = snap(text, self.anchor);
+    }
+
+    fn snapshot(&self, text: String) -> Snapshot {
+        Snapshot {
+            text,
+            cursor: self.cursor,
+            anchor: self.anchor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) -> String {
+        self.cursor = snapshot.cursor;
+        self.anchor = snapshot.anchor;
+        self.last_edit = None;
+        snapshot.text
+    }
+
+    /// Saves the text for undo before an edit, unless the edit continues a run of the same kind.
+    fn checkpoint(&mut self, text: &str, kind: EditKind) {
+        if kind == EditKind::Other || self.last_edit != Some(kind) {
+            self.undo_stack.push(self.snapshot(text.to_string()));
+        }
+        self.redo_stack.clear();
+        self.last_edit = Some(kind);
+    }
+
+    fn undo(&mut self, text: String) -> String {
+        match self.undo_stack.pop() {
+            Some(snapshot) => {
+                self.redo_stack.push(self.snapshot(text));
+                self.restore(snapshot)
+            }
+            None => text,
+        }
+    }
+
+    fn redo(&mut self, text: String) -> String {
+        match self.redo_stack.pop() {
+            Some(snapshot) => {
+                self.undo_stack.push(self.snapshot(text));
+                self.restore(snapshot)
+            }
+            None => text,
+        }
+    }
+
+    fn selected_text(&self, text: &str) -> String {
+        text[self.selection()].to_string()
+    }
+
+    fn delete_selection(&mut self, text: String) -> String {
+        let sel = self.selection();
+        let mut t = text;
+        t.replace_range(sel.clone(), "");
+        self.cursor = sel.start;
+        self.anchor = sel.start;
+        t
+    }
+
+    /// Deletes from the cursor to `to`, or the selection if there is one.
+    fn delete(&mut self, text: String, to: usize) -> String {
+        if self.has_selection() {
+            self.checkpoint(&text, EditKind::Other);
+            self.delete_selection(text)
+        } else if to != self.cursor {
+            self.checkpoint(&text, EditKind::Deleting);
+            self.anchor = to;
+            self.delete_selection(text)
+        } else {
+            text
         }
     }
 
-    fn find_line(&self) -> usize {
+    fn find_line(&self, glyph: usize) -> usize {
         let mut i = 0;
         for line in &self.lines {
-            if self.cursor >= line.glyph_start && self.cursor < line.glyph_end {
+            if glyph >= line.glyph_start && glyph < line.glyph_end {
                 break;
             }
             i += 1;
@@ -37,7 +266,7 @@ impl TextEditorState {
         range: std::ops::Range<usize>,
         rects: &[LocalRect],
     ) -> usize {
-        let mut d = std::f32::MAX;
+        let mut d = f32::MAX;
         let mut closest = 0;
         for i in range {
             let dp = rects[i].center().distance_to(p);
@@ -49,111 +278,330 @@ impl TextEditorState {
         closest
     }
 
-    fn down(&mut self) {
-        let p = self.glyph_rects[self.cursor].center();
+    /// Center of the glyph at the cursor, or just after the last glyph.
+    fn glyph_point(&self, glyph: usize) -> Option<LocalPoint> {
+        match self.glyph_rects.get(glyph) {
+            Some(r) => Some(r.center()),
+            None => self
+                .glyph_rects
+                .last()
+                .map(|r| r.center() + LocalOffset::new(r.size.width, 0.0)),
+        }
+    }
 
-        let line = self.find_line() + 1;
-        if line < self.lines.len() {
-            let metrics = self.lines[line];
-            self.cursor =
-                self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects);
+    /// Moves the cursor to the closest glyph on the previous or next line.
+    fn vertical(&mut self, text: &str, down: bool) {
+        let glyph = glyph_index(text, self.cursor);
+        if let Some(p) = self.glyph_point(glyph) {
+            let line = self.find_line(glyph);
+            let target = if down { line + 1 } else { line.wrapping_sub(1) };
+            if let Some(metrics) = self.lines.get(target) {
+                let range = metrics.glyph_start..metrics.glyph_end.min(self.glyph_rects.len());
+                let glyph = self.closest_in_range(p, range, &self.glyph_rects);
+                self.cursor = snap(text, byte_offset(text, glyph));
+            }
         }
     }
 
-    fn up(&mut self) {
-        let p = self.glyph_rects[self.cursor].center();
+    /// Glyph index for a point in the editor's local space.
+    pub(crate) fn hit(&self, pt: LocalPoint) -> usize {
+        let p = pt - self.origin;
+
+        let rects = &self.glyph_rects;
+        if rects.is_empty() {
+            return 0;
+        }
+
+        // Find the line closest vertically, then the closest glyph on that line.
+        let distance_y = |r: &LocalRect| {
+            if p.y < r.min_y() {
+                r.min_y() - p.y
+            } else if p.y > r.max_y() {
+                p.y - r.max_y()
+            } else {
+                0.0
+            }
+        };
+        let range = self
+            .lines
+            .iter()
+            .filter(|l| l.glyph_start < l.glyph_end && l.glyph_end <= rects.len())
+            .min_by(|a, b| {
+                let da = distance_y(&rects[a.glyph_start]);
+                let db = distance_y(&rects[b.glyph_start]);
+                da.total_cmp(&db)
+            })
+            .map_or(0..rects.len(), |l| l.glyph_start..l.glyph_end);
 
-        let line = self.find_line();
-        if line > 0 {
-            let metrics = self.lines[line - 1];
-            self.cursor =
-                self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects);
+        let mut i = range.start;
+        let mut d = f32::MAX;
+        for j in range {
+            let dx = (rects[j].center().x - p.x).abs();
+            if dx < d {
+                i = j;
+                d = dx;
+            }
+        }
+
+        // Place the caret after the glyph when clicking its right half.
+        if p.x > rects[i].center().x {
+            i + 1
+        } else {
+            i
         }
     }
 
-    fn key(&mut self, k: &Key, text: String) -> String {
+    pub(crate) fn key(&mut self, k: &Key, text: String, mods: KeyboardModifiers) -> String {
+        self.validate(&text);
+        let extend = mods.shift;
+
+        // Alt+arrows move by word on macOS, and Ctrl+arrows elsewhere.
+        let by_word = if cfg!(target_os = "macos") {
+            mods.alt
+        } else {
+            mods.control
+        };
+        let whole_text = mods.control || mods.command;
+
         match k {
             Key::ArrowLeft => {
-                self.back();
+                self.cursor = if self.has_selection() && !extend {
+                    self.selection().start
+                } else if by_word {
+                    prev_word(&text, self.cursor)
+                } else {
+                    prev_grapheme(&text, self.cursor)
+                };
+                self.moved(extend);
                 text
             }
             Key::ArrowRight => {
-                self.fwd(text.len());
+                self.cursor = if self.has_selection() && !extend {
+                    self.selection().end
+                } else if by_word {
+                    next_word(&text, self.cursor)
+                } else {
+                    next_grapheme(&text, self.cursor)
+                };
+                self.moved(extend);
                 text
             }
             Key::ArrowUp => {
-                self.up();
+                self.vertical(&text, false);
+                self.moved(extend);
                 text
             }
             Key::ArrowDown => {
-                self.down();
+                self.vertical(&text, true);
+                self.moved(extend);
                 text
             }
             Key::Backspace => {
-                if self.cursor > 0 {
-                    let mut t = text;
-                    t.remove(self.cursor - 1);
-                    self.back();
-                    t
-                } else {
-                    text
-                }
+                let to = prev_grapheme(&text, self.cursor);
+                self.delete(text, to)
             }
-            Key::Character(c) => {
-                let mut t = text;
-                t.insert_str(self.cursor, &format!("{}", c));
-                self.cursor += 1;
-                t
-            }
-            Key::Space => {
-                let mut t = text;
-                t.insert(self.cursor, ' ');
-                self.cursor += 1;
-                t
+            Key::Delete => {
+                let to = next_grapheme(&text, self.cursor);
+                self.delete(text, to)
             }
             Key::Home => {
-                self.cursor = 0;
+                self.cursor = if whole_text {
+                    0
+                } else {
+                    line_range(&text, self.cursor).start
+                };
+                self.moved(extend);
                 text
             }
             Key::End => {
-                self.cursor = text.len();
+                self.cursor = if whole_text {
+                    text.len()
+                } else {
+                    line_end(&text, self.cursor)
+                };
+                self.moved(extend);
                 text
             }
             _ => text,
         }
     }
+
+    pub(crate) fn text_input(&mut self, input: TextInputEvent, text: String) -> String {
+        match input {
+            TextInputEvent::Insert(s) => {
+                self.preedit.clear();
+                self.preedit_cursor = None;
+                // An empty paste or commit changes nothing to undo.
+                if s.is_empty() {
+                    return text;
+                }
+                let kind = if s.chars().count() == 1 && !self.has_selection() {
+                    EditKind::Typing
+                } else {
+                    EditKind::Other
+                };
+                self.validate(&text);
+                self.checkpoint(&text, kind);
+                let mut t = self.delete_selection(text);
+                t.insert_str(self.cursor, &s);
+                self.cursor += s.len();
+                // The inserted text may combine with the text after it, as with an accent.
+                if !is_grapheme_boundary(&t, self.cursor) {
+                    self.cursor = next_grapheme(&t, self.cursor);
+                }
+                self.collapse(false);
+                t
+            }
+            TextInputEvent::Preedit { text: s, cursor } => {
+                self.preedit_cursor = cursor.map(|(start, _)| s[..start].chars().count());
+                self.preedit = s;
+                text
+            }
+        }
+    }
+
+    /// The text as displayed, with any composition inserted at the cursor.
+    pub(crate) fn display_text(&self, text: &str) -> String {
+        let mut t = text.to_string();
+        t.insert_str(snap(text, self.cursor), &self.preedit);
+        t
+    }
 }
 
 impl TextEditorState {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             cursor: 0,
+            anchor: 0,
             glyph_rects: vec![],
             lines: vec![],
+            origin: LocalOffset::zero(),
+            preedit: String::new(),
+            preedit_cursor: None,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            last_edit: None,
         }
     }
 }
 
+/// Stores edited text in the binding. Unchanged text, for example after
+/// moving the cursor, isn't written, so it doesn't dirty the view tree.
+pub(crate) fn set_text(cx: &mut Context, text: impl Binding<String>, new_t: String) {
+    if text.with(cx, |t| *t != new_t) {
+        text.with_mut(cx, |t| *t = new_t);
+    }
+}
+
+/// Handles select all, copy, cut, paste, undo and redo.
+/// Returns false if the key isn't an editing shortcut.
+pub(crate) fn shortcut_key(
+    cx: &mut Context,
+    state: StateHandle<TextEditorState>,
+    text: impl Binding<String>,
+    k: &Key,
+) -> bool {
+    let mods = cx.key_mods;
+    if !mods.has_primary() {
+        return false;
+    }
+
+    match k {
+        Key::Character('a') => {
+            let len = text.with(cx, |t| t.len());
+            cx[state].select(0..len);
+        }
+        Key::Character('c') | Key::Character('x') => {
+            let t = text.with(cx, |t| t.clone());
+            cx[state].validate(&t);
+            if cx[state].has_selection() {
+                let selected = cx[state].selected_text(&t);
+                cx.clipboard().set_text(selected);
+                if *k == Key::Character('x') {
+                    cx[state].checkpoint(&t, EditKind::Other);
+                    let new_t = cx[state].delete_selection(t);
+                    set_text(cx, text, new_t);
+                }
+            }
+        }
+        Key::Character('v') => {
+            if let Some(pasted) = cx.clipboard().get_text() {
+                let t = text.with(cx, |t| t.clone());
+                let new_t = cx[state].text_input(TextInputEvent::Insert(pasted), t);
+                set_text(cx, text, new_t);
+            }
+        }
+        Key::Character('z') | Key::Character('Z') => {
+            let t = text.with(cx, |t| t.clone());
+            let new_t = if mods.shift {
+                cx[state].redo(t)
+            } else {
+                cx[state].undo(t)
+            };
+            set_text(cx, text, new_t);
+        }
+        _ => return false,
+    }
+    true
+}
+
 /// A multi-line text editor.
 ///
 /// This shows how a complex View with internal
 /// state can be created from more atomic Views.
+///
+/// Text is always drawn in the built-in font, ignoring the `Font` in the
+/// environment. Carets and selections are placed using vger's glyph
+/// positions, which don't cover registered fonts.
 pub fn text_editor(text: impl Binding<String>) -> impl View {
     focus(move |has_focus| {
         state(TextEditorState::new, move |state, cx| {
-            let cursor = cx[state].cursor;
+            let t = text.get(cx);
+            let s = &cx[state];
+
+            // Positions in glyphs, for drawing.
+            let preedit_len = s.preedit.chars().count();
+            let preedit_start = glyph_index(t, snap(t, s.cursor));
+            let cursor = preedit_start + s.preedit_cursor.unwrap_or(preedit_len);
+            let sel = s.selection();
+            let selection = glyph_index(t, snap(t, sel.start))..glyph_index(t, snap(t, sel.end));
+            let shown = s.display_text(t);
             canvas(move |cx, rect, vger| {
                 vger.translate([0.0, rect.height()]);
+                let theme = cx.theme();
                 let font_size = 18;
                 let break_width = Some(rect.width());
 
-                vger.text(text.get(cx), font_size, TEXT_COLOR, break_width);
+                let rects = vger.glyph_positions(&shown, font_size, break_width);
+                let lines = vger.line_metrics(&shown, font_size, break_width);
+
+                if has_focus && !selection.is_empty() {
+                    let selection_paint = vger.color_paint(theme.accent_dark);
+                    for r in rects.iter().take(selection.end).skip(selection.start) {
+                        let highlight = LocalRect::new(
+                            [r.origin.x, r.origin.y - 4.0].into(),
+                            [r.size.width, 24.0].into(),
+                        );
+                        vger.fill_rect(highlight, 0.0, selection_paint);
+                    }
+                }
+
+                vger.text(&shown, font_size, theme.text, break_width);
 
                 if has_focus {
-                    let rects = vger.glyph_positions(text.get(cx), font_size, break_width);
-                    let lines = vger.line_metrics(text.get(cx), font_size, break_width);
-                    let glyph_rect_paint = vger.color_paint(vger::Color::MAGENTA);
-                    let p = if cursor == rects.len() {
+                    let glyph_rect_paint = vger.color_paint(theme.caret);
+
+                    // Underline the text being composed.
+                    let preedit_end = (preedit_start + preedit_len).min(rects.len());
+                    for r in &rects[preedit_start.min(preedit_end)..preedit_end] {
+                        let underline = LocalRect::new(
+                            r.origin - LocalOffset::new(0.0, 2.0),
+                            [r.size.width, 1.0].into(),
+                        );
+                        vger.fill_rect(underline, 0.0, glyph_rect_paint);
+                    }
+
+                    let p = if cursor >= rects.len() {
                         if let Some(r) = rects.last() {
                             [r.origin.x + r.size.width, r.origin.y].into()
                         } else {
@@ -162,19 +610,334 @@ pub fn text_editor(text: impl Binding<String>) -> impl View {
                     } else {
                         rects[cursor].origin
                     };
-                    vger.fill_rect(LocalRect::new(p, [2.0, 20.0].into()), 0.0, glyph_rect_paint);
+                    let caret = LocalRect::new(p, [2.0, 20.0].into());
+                    vger.fill_rect(caret, 0.0, glyph_rect_paint);
+                    cx.set_ime_cursor_area(vger.current_transform().outer_transformed_rect(&caret));
+                }
 
-                    cx[state].glyph_rects = rects;
-                    cx[state].lines = lines;
+                cx[state].glyph_rects = rects;
+                cx[state].lines = lines;
+                cx[state].origin = [0.0, rect.height()].into();
+            })
+            .drag_p(move |cx, pt, gesture, _| {
+                let t = text.with(cx, |t| t.clone());
+                let extend = cx.key_mods.shift;
+                let click_count = cx.click_count();
+                let s = &mut cx[state];
+                let i = snap(&t, byte_offset(&t, s.hit(pt)));
+                s.last_edit = None;
+                match (gesture, click_count) {
+                    (GestureState::Began, 1) => {
+                        s.cursor = i;
+                        s.collapse(extend);
+                    }
+                    (GestureState::Began, 2) => s.select(word_range(&t, i)),
+                    (GestureState::Began, _) => s.select(line_range(&t, i)),
+                    (GestureState::Changed, 1) => s.cursor = i,
+                    _ => (),
                 }
             })
             .key(move |cx, k| {
+                if has_focus && !shortcut_key(cx, state, text, &k) {
+                    let t = text.with(cx, |t| t.clone());
+                    let mods = cx.key_mods;
+                    let new_t = cx[state].key(&k, t, mods);
+                    set_text(cx, text, new_t);
+                }
+            })
+            .text_input(move |cx, input| {
                 if has_focus {
                     let t = text.with(cx, |t| t.clone());
-                    let new_t = cx[state].key(&k, t);
-                    text.with_mut(cx, |t| *t = new_t);
+                    let new_t = cx[state].text_input(input, t);
+                    set_text(cx, text, new_t);
                 }
             })
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+
+    fn process_all(ui: &impl View, cx: &mut Context, events: &[Event]) {
+        let mut path = vec![0];
+        for event in events {
+            let mut actions = vec![];
+            ui.process(event, &mut path, cx, &mut actions);
+        }
+    }
+
+    #[test]
+    fn test_text_input() {
+        let mut cx = Context::new();
+
+        let ui = state(String::new, |s, _| text_editor(s));
+        let text = StateHandle::<String>::new(cx.view_id(&vec![0]));
+        let editor = cx.view_id(&vec![0, 0]);
+        cx.focus(editor);
+
+        process_all(
+            &ui,
+            &mut cx,
+            &[
+                Event::TextInput("a".into()),
+                Event::TextInput("😀é".into()),
+                Event::ImePreedit {
+                    text: "にほ".into(),
+                    cursor: Some((6, 6)),
+                },
+                Event::ImeCommit("日本".into()),
+                Event::Key(Key::Backspace),
+                Event::TextInput("b".into()),
+            ],
+        );
+
+        assert_eq!(cx[text], "a😀é日b");
+
+        // Moving the cursor doesn't write the text.
+        cx.clear_dirty();
+        process_all(&ui, &mut cx, &[Event::Key(Key::ArrowLeft)]);
+        assert!(!cx.is_dirty(text.id));
+    }
+
+    #[test]
+    fn test_selection_clipboard() {
+        let mut cx = Context::new();
+
+        let ui = state(|| "hello world".to_string(), |s, _| text_editor(s));
+        let text = StateHandle::<String>::new(cx.view_id(&vec![0]));
+        let editor = cx.view_id(&vec![0, 0]);
+        cx.focus(editor);
+
+        let primary = KeyboardModifiers::primary();
+
+        // Select "hello" with shift+arrows, then cut it.
+        cx.key_mods.shift = true;
+        process_all(&ui, &mut cx, &vec![Event::Key(Key::ArrowRight); 5]);
+        cx.key_mods = primary;
+        process_all(&ui, &mut cx, &[Event::Key(Key::Character('x'))]);
+        assert_eq!(cx[text], " world");
+        assert_eq!(cx.clipboard().get_text().as_deref(), Some("hello"));
+
+        // Paste at the end.
+        cx.key_mods = Default::default();
+        process_all(&ui, &mut cx, &[Event::Key(Key::End)]);
+        cx.key_mods = primary;
+        process_all(&ui, &mut cx, &[Event::Key(Key::Character('v'))]);
+        assert_eq!(cx[text], " worldhello");
+
+        // Typing replaces the whole selection.
+        process_all(&ui, &mut cx, &[Event::Key(Key::Character('a'))]);
+        cx.key_mods = Default::default();
+        process_all(&ui, &mut cx, &[Event::TextInput("x".into())]);
+        assert_eq!(cx[text], "x");
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut cx = Context::new();
+
+        let ui = state(String::new, |s, _| text_editor(s));
+        let text = StateHandle::<String>::new(cx.view_id(&vec![0]));
+        let editor = cx.view_id(&vec![0, 0]);
+        cx.focus(editor);
+
+        let typing: Vec<Event> = "abc".chars().map(|c| Event::TextInput(c.into())).collect();
+        process_all(&ui, &mut cx, &typing);
+
+        // Moving the cursor starts a new run of typing.
+        process_all(&ui, &mut cx, &[Event::Key(Key::ArrowLeft)]);
+        process_all(&ui, &mut cx, &typing);
+        process_all(
+            &ui,
+            &mut cx,
+            &[Event::Key(Key::Backspace), Event::Key(Key::Backspace)],
+        );
+        assert_eq!(cx[text], "abac");
+
+        let mut undo = KeyboardModifiers::primary();
+        let undo_key = [Event::Key(Key::Character('z'))];
+        cx.key_mods = undo;
+        process_all(&ui, &mut cx, &undo_key);
+        assert_eq!(cx[text], "ababcc");
+        process_all(&ui, &mut cx, &undo_key);
+        assert_eq!(cx[text], "abc");
+        process_all(&ui, &mut cx, &undo_key);
+        assert_eq!(cx[text], "");
+
+        undo.shift = true;
+        cx.key_mods = undo;
+        process_all(&ui, &mut cx, &undo_key);
+        process_all(&ui, &mut cx, &undo_key);
+        assert_eq!(cx[text], "ababcc");
+    }
+
+    #[test]
+    fn test_empty_insert() {
+        let mut s = TextEditorState::new();
+        let t = s.text_input(TextInputEvent::Insert("ab".into()), String::new());
+        s.select(0..1);
+        let undo_depth = s.undo_stack.len();
+
+        let t = s.text_input(TextInputEvent::Insert(String::new()), t);
+        assert_eq!(t, "ab");
+        assert_eq!(s.undo_stack.len(), undo_depth);
+        assert!(s.has_selection());
+    }
+
+    #[test]
+    fn test_word_and_line_ranges() {
+        let text = "one two, three\nfour";
+        assert_eq!(word_range(text, 5), 4..7);
+        assert_eq!(word_range(text, 7), 7..8);
+        assert_eq!(word_range(text, 3), 3..4);
+        assert_eq!(line_range(text, 5), 0..15);
+        assert_eq!(line_range(text, 16), 15..19);
+        assert_eq!(word_range("", 0), 0..0);
+    }
+
+    #[test]
+    fn test_hit() {
+        let mut s = TextEditorState::new();
+        s.origin = [0.0, 20.0].into();
+        // Two glyphs per line, two lines, laid out down from the top.
+        s.glyph_rects = vec![
+            LocalRect::new([0.0, -20.0].into(), [10.0, 20.0].into()),
+            LocalRect::new([10.0, -20.0].into(), [10.0, 20.0].into()),
+            LocalRect::new([0.0, -40.0].into(), [10.0, 20.0].into()),
+            LocalRect::new([10.0, -40.0].into(), [10.0, 20.0].into()),
+        ];
+        s.lines = vec![
+            LineMetrics {
+                glyph_start: 0,
+                glyph_end: 2,
+                bounds: LocalRect::zero(),
+            },
+            LineMetrics {
+                glyph_start: 2,
+                glyph_end: 4,
+                bounds: LocalRect::zero(),
+            },
+        ];
+        assert_eq!(s.hit([2.0, 10.0].into()), 0);
+        assert_eq!(s.hit([8.0, 10.0].into()), 1);
+        assert_eq!(s.hit([18.0, -10.0].into()), 4);
+    }
+
+    #[test]
+    fn test_unicode_editing() {
+        let mut s = TextEditorState::new();
+        let none = KeyboardModifiers::default();
+        let word = if cfg!(target_os = "macos") {
+            KeyboardModifiers {
+                alt: true,
+                ..Default::default()
+            }
+        } else {
+            KeyboardModifiers {
+                control: true,
+                ..Default::default()
+            }
+        };
+
+        // "e" with a combining accent, and a family emoji, are single graphemes.
+        let mut t = "ae\u{301}👨‍👩‍👧 héllo wörld\nsecond line".to_string();
+        t = s.key(&Key::ArrowRight, t, none);
+        t = s.key(&Key::ArrowRight, t, none);
+        assert_eq!(s.cursor, "ae\u{301}".len());
+        t = s.key(&Key::Delete, t, none);
+        assert!(t.starts_with("ae\u{301} héllo"));
+        t = s.key(&Key::Backspace, t, none);
+        assert!(t.starts_with("a héllo"));
+
+        t = s.key(&Key::ArrowRight, t, word);
+        assert_eq!(s.cursor, "a héllo".len());
+        t = s.key(&Key::ArrowRight, t, word);
+        assert_eq!(s.cursor, "a héllo wörld".len());
+        t = s.key(&Key::ArrowLeft, t, word);
+        assert_eq!(s.cursor, "a héllo ".len());
+
+        t = s.key(&Key::End, t, none);
+        assert_eq!(s.cursor, "a héllo wörld".len());
+        t = s.key(&Key::ArrowRight, t, none);
+        t = s.key(&Key::End, t, none);
+        assert_eq!(s.cursor, t.len());
+        t = s.key(&Key::Home, t, none);
+        assert_eq!(&t[s.cursor..], "second line");
+    }
+
+    fn random_key(i: usize) -> (Key, KeyboardModifiers) {
+        let shift = KeyboardModifiers {
+            shift: true,
+            ..Default::default()
+        };
+        let word = KeyboardModifiers {
+            alt: true,
+            control: true,
+            ..Default::default()
+        };
+        let keys = [
+            Key::ArrowLeft,
+            Key::ArrowRight,
+            Key::ArrowUp,
+            Key::ArrowDown,
+            Key::Home,
+            Key::End,
+            Key::Backspace,
+            Key::Delete,
+        ];
+        let mods = [KeyboardModifiers::default(), shift, word];
+        (keys[i % keys.len()], mods[i / keys.len() % mods.len()])
+    }
+
+    proptest! {
+        #[test]
+        fn prop_cursor_on_grapheme_boundaries(
+            text in "(\\PC|\u{200D}){0,24}",
+            ops in prop::collection::vec((0usize..24, "(\\PC|\u{200D}){0,3}"), 0..32),
+        ) {
+            let mut s = TextEditorState::new();
+            let mut t = text;
+            for (op, input) in ops {
+                t = if op < 20 {
+                    let (k, mods) = random_key(op);
+                    s.key(&k, t, mods)
+                } else {
+                    s.text_input(TextInputEvent::Insert(input), t)
+                };
+                prop_assert_eq!(snap(&t, s.cursor), s.cursor);
+                prop_assert_eq!(snap(&t, s.anchor), s.anchor);
+            }
+        }
+
+        #[test]
+        fn prop_arrows_step_by_grapheme(text in "(\\PC|\u{200D}){0,24}") {
+            let mut s = TextEditorState::new();
+            let none = KeyboardModifiers::default();
+            let count = text.graphemes(true).count();
+            let mut t = text.clone();
+            for _ in 0..count {
+                t = s.key(&Key::ArrowRight, t, none);
+            }
+            prop_assert_eq!(s.cursor, text.len());
+            for _ in 0..count {
+                t = s.key(&Key::ArrowLeft, t, none);
+            }
+            prop_assert_eq!(s.cursor, 0);
+            prop_assert_eq!(t, text);
+        }
+
+        #[test]
+        fn prop_insert_undo(text in "(\\PC|\u{200D}){0,24}", at in 0usize..64, input in "\\PC{1,4}") {
+            let mut s = TextEditorState::new();
+            s.cursor = at;
+            s.anchor = at;
+            let t = s.text_input(TextInputEvent::Insert(input.clone()), text.clone());
+            prop_assert!(t.contains(&input));
+            prop_assert_eq!(s.undo(t), text);
+        }
+    }
+}
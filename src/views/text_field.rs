@@ -0,0 +1,413 @@
+use crate::*;
+use std::sync::Arc;
+
+const TEXT_FIELD_FONT_SIZE: u32 = 18;
+const TEXT_FIELD_PADDING: f32 = 6.0;
+const TEXT_FIELD_CORNER_RADIUS: f32 = 4.0;
+
+/// Character shown instead of each character of a password.
+const PASSWORD_MASK: char = '•';
+
+/// Restricts what can be typed into a `text_field`.
+///
+/// The filter is applied to the whole text after each edit, and
+/// edits which produce text the filter rejects are ignored.
+#[derive(Clone)]
+pub enum InputFilter {
+    /// Only ASCII digits.
+    Digits,
+
+    /// The text must match the regular expression. Anchor it with `^...$`
+    /// to constrain the whole text.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+
+    /// The text must satisfy the predicate.
+    Custom(Arc<dyn Fn(&str) -> bool>),
+}
+
+impl InputFilter {
+    pub fn custom(f: impl Fn(&str) -> bool + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+
+    pub fn accepts(&self, text: &str) -> bool {
+        match self {
+            Self::Digits => text.chars().all(|c| c.is_ascii_digit()),
+            #[cfg(feature = "regex")]
+            Self::Regex(re) => re.is_match(text),
+            Self::Custom(f) => f(text),
+        }
+    }
+}
+
+type SubmitFn = Arc<dyn Fn(&mut Context)>;
+
+#[derive(Clone, Default)]
+pub struct TextFieldOptions {
+    pub(crate) placeholder: String,
+    pub(crate) on_submit: Option<SubmitFn>,
+    pub(crate) max_length: Option<usize>,
+    pub(crate) filter: Option<InputFilter>,
+    pub(crate) password: bool,
+    pub(crate) error: bool,
+}
+
+impl TextFieldOptions {
+    /// Is the text allowed in the field?
+    fn accepts(&self, text: &str) -> bool {
+        use unicode_segmentation::UnicodeSegmentation;
+        if let Some(max) = self.max_length {
+            if text.graphemes(true).count() > max {
+                return false;
+            }
+        }
+        match &self.filter {
+            Some(filter) => filter.accepts(text),
+            None => true,
+        }
+    }
+}
+
+pub trait TextFieldMods: View + Sized {
+    /// Text shown in a dimmed color while the field is empty.
+    fn placeholder(self, placeholder: &str) -> Self;
+
+    /// Called when Enter is pressed in the field.
+    fn on_submit(self, f: impl Fn(&mut Context) + 'static) -> Self;
+
+    /// Limits the number of characters (graphemes) in the field.
+    fn max_length(self, max_length: usize) -> Self;
+
+    /// Restricts what can be typed or pasted into the field.
+    fn input_filter(self, filter: InputFilter) -> Self;
+
+    /// Hides the text, for passwords.
+    fn password(self, password: bool) -> Self;
+
+    /// Shows the field in an error state, for example when validation failed.
+    fn error(self, error: bool) -> Self;
+}
+
+impl<F> TextFieldMods for ModView<TextFieldOptions, F>
+where
+    ModView<TextFieldOptions, F>: View,
+{
+    fn placeholder(mut self, placeholder: &str) -> Self {
+        self.value.placeholder = placeholder.into();
+        self
+    }
+
+    fn on_submit(mut self, f: impl Fn(&mut Context) + 'static) -> Self {
+        self.value.on_submit = Some(Arc::new(f));
+        self
+    }
+
+    fn max_length(mut self, max_length: usize) -> Self {
+        self.value.max_length = Some(max_length);
+        self
+    }
+
+    fn input_filter(mut self, filter: InputFilter) -> Self {
+        self.value.filter = Some(filter);
+        self
+    }
+
+    fn password(mut self, password: bool) -> Self {
+        self.value.password = password;
+        self
+    }
+
+    fn error(mut self, error: bool) -> Self {
+        self.value.error = error;
+        self
+    }
+}
+
+/// Applies an edit to the field, then undoes it if the options reject the result.
+fn edit_field(
+    cx: &mut Context,
+    state: StateHandle<TextEditorState>,
+    text: impl Binding<String>,
+    opts: &TextFieldOptions,
+    edit: impl FnOnce(&mut Context),
+) {
+    let old_state = cx[state].clone();
+    let old_text = text.with(cx, |t| t.clone());
+
+    edit(cx);
+
+    // A single line can't contain newlines. Spaces have the same
+    // length in bytes, so the cursor stays valid.
+    let t = text.with(cx, |t| t.replace(['\n', '\r'], " "));
+
+    if t != old_text && !opts.accepts(&t) {
+        cx[state] = old_state;
+        text.with_mut(cx, |t| *t = old_text);
+    } else if t != *text.get(cx) {
+        text.with_mut(cx, |s| *s = t);
+    }
+}
+
+/// A single-line text field.
+///
+/// Use `.size` to give it a height, as it fills the space it is offered.
+///
+/// Like `text_editor`, it ignores the `Font` in the environment.
+pub fn text_field(text: impl Binding<String>) -> impl TextFieldMods {
+    modview(move |opts: TextFieldOptions, _| {
+        let role = if opts.password {
+            accesskit::Role::PasswordInput
+        } else {
+            accesskit::Role::TextInput
+        };
+        focus(move |has_focus| {
+            let opts = opts.clone();
+            state(TextEditorState::new, move |state, cx| {
+                text_field_body(text, opts.clone(), has_focus, state, cx)
+            })
+        })
+        .role(role)
+    })
+}
+
+/// The editing part of a `text_field`, for views which manage the
+/// focus and editor state themselves, like `number_field`.
+pub(crate) fn text_field_body(
+    text: impl Binding<String>,
+    opts: TextFieldOptions,
+    has_focus: bool,
+    state: StateHandle<TextEditorState>,
+    cx: &Context,
+) -> impl View {
+    let t = text.get(cx);
+    let s = &cx[state];
+
+    // Positions in glyphs, for drawing.
+    let preedit_len = s.preedit.chars().count();
+    let preedit_start = glyph_index(t, snap(t, s.cursor));
+    let cursor = preedit_start + s.preedit_cursor.unwrap_or(preedit_len);
+    let sel = s.selection();
+    let selection = glyph_index(t, snap(t, sel.start))..glyph_index(t, snap(t, sel.end));
+    let mut shown = s.display_text(t);
+    if opts.password {
+        // Keep one glyph per character so glyph indices still match.
+        shown = shown.chars().map(|_| PASSWORD_MASK).collect();
+    }
+    let scroll = TEXT_FIELD_PADDING - s.origin.x;
+    let key_opts = opts.clone();
+    let input_opts = opts.clone();
+    let password = opts.password;
+
+    canvas(move |cx, rect, vger| {
+        let theme = cx.theme();
+        let background = vger.color_paint(theme.control_background);
+        vger.fill_rect(rect, TEXT_FIELD_CORNER_RADIUS, background);
+
+        let border = if opts.error {
+            Some(theme.error)
+        } else if has_focus {
+            Some(theme.accent_dark)
+        } else {
+            None
+        };
+        if let Some(color) = border {
+            let paint = vger.color_paint(color);
+            vger.stroke_rect(rect.min(), rect.max(), TEXT_FIELD_CORNER_RADIUS, 1.0, paint);
+        }
+
+        let rects = vger.glyph_positions(&shown, TEXT_FIELD_FONT_SIZE, None);
+        let caret_x = match rects.get(cursor) {
+            Some(r) => r.origin.x,
+            None => rects.last().map_or(0.0, |r| r.max_x()),
+        };
+
+        // Scroll horizontally to keep the caret visible.
+        let visible = (rect.width() - 2.0 * TEXT_FIELD_PADDING).max(0.0);
+        let mut scroll = scroll.min(caret_x).max(caret_x - visible);
+        let text_width = rects.last().map_or(0.0, |r| r.max_x());
+        scroll = scroll.min((text_width - visible).max(0.0)).max(0.0);
+
+        let origin = LocalOffset::new(
+            TEXT_FIELD_PADDING - scroll,
+            (rect.height() + TEXT_FIELD_FONT_SIZE as f32) / 2.0,
+        );
+
+        vger.save();
+        vger.scissor(rect.inflate(-TEXT_FIELD_PADDING / 2.0, 0.0));
+        vger.translate(origin);
+
+        if has_focus && !selection.is_empty() {
+            let selection_paint = vger.color_paint(theme.accent_dark);
+            for r in rects.iter().take(selection.end).skip(selection.start) {
+                let highlight = LocalRect::new(
+                    [r.origin.x, r.origin.y - 4.0].into(),
+                    [r.size.width, 24.0].into(),
+                );
+                vger.fill_rect(highlight, 0.0, selection_paint);
+            }
+        }
+
+        if shown.is_empty() {
+            vger.text(
+                &opts.placeholder,
+                TEXT_FIELD_FONT_SIZE,
+                theme.secondary_text,
+                None,
+            );
+        } else {
+            vger.text(&shown, TEXT_FIELD_FONT_SIZE, theme.text, None);
+        }
+
+        if has_focus {
+            let caret_paint = vger.color_paint(theme.caret);
+
+            // Underline the text being composed.
+            let preedit_end = (preedit_start + preedit_len).min(rects.len());
+            for r in &rects[preedit_start.min(preedit_end)..preedit_end] {
+                let underline = LocalRect::new(
+                    r.origin - LocalOffset::new(0.0, 2.0),
+                    [r.size.width, 1.0].into(),
+                );
+                vger.fill_rect(underline, 0.0, caret_paint);
+            }
+
+            let caret = LocalRect::new([caret_x, -20.0].into(), [2.0, 20.0].into());
+            vger.fill_rect(caret, 0.0, caret_paint);
+            cx.set_ime_cursor_area(vger.current_transform().outer_transformed_rect(&caret));
+        }
+
+        vger.restore();
+
+        cx[state].lines = vec![];
+        cx[state].glyph_rects = rects;
+        cx[state].origin = origin;
+    })
+    .drag_p(move |cx, pt, gesture, _| {
+        let t = text.with(cx, |t| t.clone());
+        let extend = cx.key_mods.shift;
+        let click_count = cx.click_count();
+        let s = &mut cx[state];
+        let i = snap(&t, byte_offset(&t, s.hit(pt)));
+        s.last_edit = None;
+        match (gesture, click_count) {
+            (GestureState::Began, 1) => {
+                s.cursor = i;
+                s.collapse(extend);
+            }
+            // Word boundaries would give away the password.
+            (GestureState::Began, 2) if !password => s.select(word_range(&t, i)),
+            (GestureState::Began, _) => s.select(0..t.len()),
+            (GestureState::Changed, 1) => s.cursor = i,
+            _ => (),
+        }
+    })
+    .key(move |cx, k| {
+        if !has_focus {
+            return;
+        }
+        if k == Key::Enter {
+            if let Some(f) = &key_opts.on_submit {
+                f(cx);
+            }
+            return;
+        }
+        // Passwords can't be copied out of the field.
+        let copy = matches!(k, Key::Character('c') | Key::Character('x'));
+        if key_opts.password && cx.key_mods.has_primary() && copy {
+            return;
+        }
+        edit_field(cx, state, text, &key_opts, |cx| {
+            if !shortcut_key(cx, state, text, &k) {
+                let t = text.with(cx, |t| t.clone());
+                let mods = cx.key_mods;
+                let new_t = cx[state].key(&k, t, mods);
+                set_text(cx, text, new_t);
+            }
+        })
+    })
+    .text_input(move |cx, input| {
+        if has_focus {
+            edit_field(cx, state, text, &input_opts, |cx| {
+                let t = text.with(cx, |t| t.clone());
+                let new_t = cx[state].text_input(input, t);
+                set_text(cx, text, new_t);
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_text_field() {
+        let mut cx = Context::new();
+
+        let ui = state(String::new, |text, _| {
+            state(
+                || 0,
+                move |submits, _| {
+                    text_field(text)
+                        .max_length(4)
+                        .input_filter(InputFilter::Digits)
+                        .on_submit(move |cx| cx[submits] += 1)
+                },
+            )
+        });
+        let text = StateHandle::<String>::new(cx.view_id(&vec![0]));
+        let submits = StateHandle::<i32>::new(cx.view_id(&vec![0, 0]));
+        cx.focus_next(&ui, false);
+
+        let mut path = vec![0];
+        for event in [
+            Event::TextInput("12".into()),
+            Event::TextInput("a".into()),
+            Event::TextInput("345".into()),
+            Event::TextInput("3".into()),
+            Event::TextInput("4".into()),
+            Event::TextInput("5".into()),
+            Event::Key(Key::Enter),
+        ] {
+            ui.process(&event, &mut path, &mut cx, &mut vec![]);
+        }
+
+        assert_eq!(cx[text], "1234");
+        assert_eq!(cx[submits], 1);
+    }
+
+    #[test]
+    fn test_password_double_click() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || "ab cd".to_string(),
+            |text, _| text_field(text).password(true),
+        );
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 30.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+
+        // Selects the whole password rather than a word.
+        cx.click_count = 2;
+        let position = [10.0, 15.0].into();
+        cx.process(&ui, &Event::TouchBegin { id: 0, position });
+        cx.process(&ui, &Event::TouchEnd { id: 0, position });
+
+        let selection = cx
+            .state_map
+            .values()
+            .find_map(|h| h.state.downcast_ref::<TextEditorState>())
+            .unwrap()
+            .selection();
+        assert_eq!(selection, 0..5);
+    }
+}
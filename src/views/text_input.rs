@@ -0,0 +1,131 @@
+use crate::*;
+use std::any::Any;
+
+/// Text entered by the user, received by the `text_input` modifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextInputEvent {
+    /// Text to insert at the cursor. Ends any composition in progress.
+    Insert(String),
+
+    /// The IME is composing text which should be shown at the cursor,
+    /// replacing the previous composition. An empty string ends the composition.
+    Preedit {
+        text: String,
+        /// Byte range of the composition cursor within `text`, if it should be shown.
+        cursor: Option<(usize, usize)>,
+    },
+}
+
+/// Struct for the `text_input` modifier.
+#[derive(Clone)]
+pub struct TextInputView<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F> TextInputView<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, TextInputEvent) + Clone + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        TextInputView { child: v, func: f }
+    }
+}
+
+impl<V, F> DynView for TextInputView<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, TextInputEvent) + Clone + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        match event {
+            Event::TextInput(text) | Event::ImeCommit(text) => {
+                (self.func)(cx, TextInputEvent::Insert(text.clone()))
+            }
+            Event::ImePreedit { text, cursor } => (self.func)(
+                cx,
+                TextInputEvent::Preedit {
+                    text: text.clone(),
+                    cursor: *cursor,
+                },
+            ),
+            _ => (),
+        }
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        // Marks the focus view this is in as editing text.
+        cx.found_text_input = true;
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for TextInputView<V, F> {}
@@ -1,4 +1,5 @@
 use crate::*;
+use std::sync::Arc;
 
 pub struct Toggle;
 
@@ -14,9 +15,11 @@ pub struct ToggleConfig {
     height: f32,
     edge: f32,
     animation_speed: f32,
-    background_on: Color,
-    background_off: Color,
-    knob_color: Color,
+
+    /// Colors from the theme, unless set.
+    background_on: Option<Color>,
+    background_off: Option<Color>,
+    knob_color: Option<Color>,
 }
 
 impl Default for ToggleConfig {
@@ -26,9 +29,9 @@ impl Default for ToggleConfig {
             height: 20.0,
             edge: 1.0,
             animation_speed: 15.0,
-            background_on: AZURE_HIGHLIGHT_BACKGROUND,
-            background_off: CONTROL_BACKGROUND,
-            knob_color: MEDIUM_GRAY,
+            background_on: None,
+            background_off: None,
+            knob_color: None,
         }
     }
 }
@@ -55,9 +58,9 @@ impl ToggleConfig {
     }
 
     pub fn colors(mut self, background_on: Color, background_off: Color, knob: Color) -> Self {
-        self.background_on = background_on;
-        self.background_off = background_off;
-        self.knob_color = knob;
+        self.background_on = Some(background_on);
+        self.background_off = Some(background_off);
+        self.knob_color = Some(knob);
         self
     }
 
@@ -66,57 +69,115 @@ impl ToggleConfig {
     }
 }
 
-pub fn toggle_with_config(on: impl Binding<bool>, config: ToggleConfig) -> impl View {
-    let double_edge = config.edge * 2.0;
-    let knob_size = config.height - double_edge;
-    let travel_distance = config.width - config.height;
+impl ToggleStyle for ToggleConfig {
+    fn body(&self, config: ToggleConfiguration, theme: &Theme) -> AnyView {
+        let knob_size = self.height - self.edge * 2.0;
+        let travel_distance = self.width - self.height;
+
+        any_view(zstack((
+            // Background rectangle
+            rectangle()
+                .color(if config.on {
+                    self.background_on.unwrap_or(theme.accent_background)
+                } else {
+                    self.background_off.unwrap_or(theme.control_background)
+                })
+                .corner_radius(self.height / 2.0) // Make corners perfectly round
+                .size([self.width, self.height]),
+            // Knob circle
+            circle()
+                .color(self.knob_color.unwrap_or(theme.thumb))
+                .shadow(BLACK.alpha(0.2), 2.0, [0.0, -1.0])
+                .size([knob_size, knob_size])
+                .offset([config.position * travel_distance + self.edge, self.edge]),
+        )))
+    }
+}
+
+struct ToggleState {
+    position: Animated<f32>,
+    hovered: bool,
+    down: bool,
+}
+
+impl Default for ToggleState {
+    fn default() -> Self {
+        Self {
+            position: Animated::new(0.0),
+            hovered: false,
+            down: false,
+        }
+    }
+}
 
-    state(
-        || 0.0f32,
-        move |animation: StateHandle<f32>, cx| {
+/// Toggle drawn by `style`, or by the style from the environment if `None`.
+fn styled_toggle(
+    on: impl Binding<bool>,
+    style: Option<AnyToggleStyle>,
+    animation_speed: f32,
+) -> impl View {
+    // Settles in about the time an exponential approach at `animation_speed` would.
+    let animation = (animation_speed > 0.0).then(|| Animation::ease_in_out(4.0 / animation_speed));
+    keyboard_focus(move |has_focus| {
+        let style = style.clone();
+        state(ToggleState::default, move |s, cx| {
             let is_on = *on.get(cx);
+            let config = ToggleConfiguration {
+                on: is_on,
+                position: cx[s].position.value(),
+                state: ControlState {
+                    hovered: cx[s].hovered,
+                    pressed: cx[s].down,
+                    focused: has_focus,
+                    disabled: cx.is_disabled(),
+                },
+            };
+            let style = style.clone().unwrap_or_else(|| cx.get_env());
 
-            let animation_speed = config.animation_speed;
-
-            zstack((
-                // Background rectangle
-                rectangle()
-                    .color(if is_on {
-                        config.background_on
-                    } else {
-                        config.background_off
-                    })
-                    .corner_radius(config.height / 2.0) // Make corners perfectly round
-                    .size([config.width, config.height])
-                    .tap(move |cx| {
+            style
+                .body(config, &cx.theme())
+                .touch(move |cx, info| match info.state {
+                    TouchState::Begin => {
+                        cx[s].down = true;
+                    }
+                    TouchState::End => {
+                        cx[s].down = false;
+                        if cx[s].hovered && !cx.is_disabled() {
+                            on.with_mut(cx, |b| *b = !*b);
+                        }
+                    }
+                })
+                .hover(move |cx, inside| {
+                    cx[s].hovered = inside;
+                })
+                .key(move |cx, key| {
+                    if has_focus && matches!(key, Key::Space | Key::Enter) {
                         on.with_mut(cx, |b| *b = !*b);
-                    }),
-                // .hover_cursor(CursorStyle::Pointer), // Add pointer cursor on hover
-                // Knob circle
-                circle()
-                    .color(config.knob_color)
-                    .size([knob_size, knob_size])
-                    .offset([cx[animation] * travel_distance + config.edge, config.edge]), // .shadow(2.0, [0.0, 1.0], Color::BLACK.with_alpha(0.1)), // Add subtle shadow
-            ))
-            .anim(move |cx, dt| {
-                let target = if is_on { 1.0 } else { 0.0 };
-                let delta = target - cx[animation];
-
-                cx[animation] += delta * animation_speed * dt;
-
-                // Snap to final position when very close
-                if delta.abs() < 0.01 {
-                    cx[animation] = target;
-                }
-            })
-        },
-    )
+                    }
+                })
+                .anim(move |cx, dt| {
+                    let target = if is_on { 1.0 } else { 0.0 };
+                    cx[s].position.set(target, animation);
+                    if cx[s].position.step(dt) {
+                        cx.request_frame();
+                    }
+                })
+                .role(accesskit::Role::Switch)
+        })
+    })
 }
 
-/// A toggle switch with default configuration.
+/// A toggle switch drawn with `config`, ignoring the `toggle_style`.
+pub fn toggle_with_config(on: impl Binding<bool>, config: ToggleConfig) -> impl View {
+    let animation_speed = config.animation_speed;
+    styled_toggle(on, Some(AnyToggleStyle(Arc::new(config))), animation_speed)
+}
+
+/// A toggle switch drawn with the `ToggleStyle` set with `toggle_style`.
 pub fn toggle(on: impl Binding<bool>) -> impl View {
-    toggle_with_config(on, ToggleConfig::default())
+    styled_toggle(on, None, ToggleConfig::default().animation_speed)
 }
+
 #[cfg(test)]
 mod tests {
 
@@ -161,5 +222,53 @@ mod tests {
 
         // State should have changed.
         assert_eq!(*s.get(&cx), true);
+
+        // The knob moves with the clock, and frames stop once it gets there.
+        let clock = VirtualClock::new();
+        cx.set_clock(clock.clone());
+        let knob = StateHandle::<ToggleState>::new(cx.view_id(&vec![0, 0, 0]));
+        cx.animate(&ui);
+        assert!(cx[knob].position.value() > 0.0);
+        assert!(cx.wants_frame());
+        for _ in 0..30 {
+            clock.advance(1.0 / 60.0);
+            cx.animate(&ui);
+        }
+        assert_eq!(cx[knob].position.value(), 1.0);
+        assert!(!cx.wants_frame());
+    }
+
+    #[test]
+    fn test_disabled_mid_press() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || false,
+            |on, _| {
+                state(
+                    || false,
+                    move |disabled, cx| toggle(on).disabled(cx[disabled]),
+                )
+            },
+        );
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [40.0, 20.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let on = StateHandle::<bool>::new(cx.view_id(&vec![0]));
+        let disabled = StateHandle::<bool>::new(cx.view_id(&vec![0, 0]));
+
+        let position = [10.0, 10.0].into();
+        cx.process(&ui, &Event::TouchBegin { id: 0, position });
+        cx[disabled] = true;
+        cx.process(&ui, &Event::TouchEnd { id: 0, position });
+
+        assert!(!cx[on]);
+        assert_eq!(cx.touches[0], ViewId::default());
     }
 }
@@ -0,0 +1,321 @@
+use crate::*;
+use euclid::Transform2D;
+use std::any::Any;
+
+/// Struct for the `rotate`, `scale` and `transform` modifiers.
+#[derive(Clone)]
+pub struct TransformView<V> {
+    child: V,
+    xform: LocalTransform,
+
+    /// Point the transform is applied about, relative to the view's size.
+    anchor: LocalPoint,
+}
+
+/// Inverse of the transform the last time it had one.
+#[derive(Default)]
+struct LastInverse(LocalTransform);
+
+impl<V> TransformView<V>
+where
+    V: View,
+{
+    pub fn new(child: V, xform: LocalTransform, anchor: LocalPoint) -> Self {
+        Self {
+            child,
+            xform,
+            anchor,
+        }
+    }
+
+    /// The transform from the child's space to ours, about the anchor.
+    fn full_transform(&self, path: &IdPath, cx: &Context) -> LocalTransform {
+        let size = cx.get_layout(path).rect.size;
+        let anchor = LocalOffset::new(self.anchor.x * size.width, self.anchor.y * size.height);
+        LocalTransform::translation(-anchor.x, -anchor.y)
+            .then(&self.xform)
+            .then_translate(anchor)
+    }
+}
+
+impl<V> DynView for TransformView<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let id = cx.view_id(path);
+        let event = match self.full_transform(path, cx).inverse() {
+            Some(inverse) => {
+                cx.view_state(id, LastInverse::default).0 = inverse;
+                event.transform(&inverse)
+            }
+            // Can't map positions into a view scaled to nothing. Touches
+            // must still end, so map those as the view was last shown,
+            // or untransformed if it never was.
+            None => match event {
+                Event::TouchBegin { .. } | Event::TouchMove { .. } => return,
+                Event::TouchEnd { .. } => {
+                    let inverse = cx.view_state(id, LastInverse::default).0;
+                    event.transform(&inverse)
+                }
+                _ => event.clone(),
+            },
+        };
+        path.push(0);
+        self.child.process(&event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let xform = self.full_transform(path, args.cx);
+        args.vger.save();
+        apply_transform(args.vger, &xform);
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let local = self.full_transform(path, cx);
+        path.push(0);
+        self.child.dirty(path, local.then(&xform), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let inverse = self.full_transform(path, cx).inverse()?;
+        path.push(0);
+        let hit_id = self.child.hittest(path, inverse.transform_point(pt), cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        let local = self.full_transform(path, cx);
+        path.push(0);
+        self.child.focusables(path, local.then(&xform), cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for TransformView<V> {}
+
+/// Splits the linear part of a transform into a rotation, a scale and
+/// another rotation, which is all vger can compose. Returns the
+/// translation, the first rotation, the scale and the second rotation,
+/// in the order to apply them with vger.
+fn decompose<Src, Dst>(xform: &Transform2D<f32, Src, Dst>) -> (LocalOffset, f32, LocalOffset, f32) {
+    // Singular value decomposition of a 2x2 matrix.
+    let e = (xform.m11 + xform.m22) / 2.0;
+    let f = (xform.m11 - xform.m22) / 2.0;
+    let g = (xform.m12 + xform.m21) / 2.0;
+    let h = (xform.m12 - xform.m21) / 2.0;
+    let q = e.hypot(h);
+    let r = f.hypot(g);
+    let a1 = g.atan2(f);
+    let a2 = h.atan2(e);
+    (
+        LocalOffset::new(xform.m31, xform.m32),
+        (a2 + a1) / 2.0,
+        LocalOffset::new(q + r, q - r),
+        (a2 - a1) / 2.0,
+    )
+}
+
+/// Composes a transform with vger's current one.
+pub(crate) fn apply_transform<Src, Dst>(vger: &mut Vger, xform: &Transform2D<f32, Src, Dst>) {
+    let (offset, first, scale, second) = decompose(xform);
+    vger.translate(offset);
+    vger.rotate(first);
+    vger.scale(scale);
+    vger.rotate(second);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use euclid::Angle;
+
+    #[test]
+    fn test_decompose() {
+        let xforms = [
+            LocalTransform::rotation(Angle::radians(0.7)),
+            LocalTransform::scale(2.0, 0.5).then_rotate(Angle::radians(-2.0)),
+            LocalTransform::scale(-1.0, 1.0).then_translate([3.0, 4.0].into()),
+            LocalTransform::new(1.0, 0.0, 0.5, 1.0, 10.0, -2.0),
+        ];
+        for xform in xforms {
+            // Compose the same way vger does.
+            let (offset, first, scale, second) = decompose(&xform);
+            let composed = LocalTransform::identity()
+                .pre_translate(offset)
+                .pre_rotate(Angle::radians(first))
+                .pre_scale(scale.x, scale.y)
+                .pre_rotate(Angle::radians(second));
+            assert!(composed.approx_eq_eps(&xform, &1e-5), "{:?}", xform);
+        }
+    }
+
+    #[test]
+    fn test_rotate_hittest() {
+        let mut cx = Context::new();
+
+        // A wide bar turned upright about its center.
+        let ui = rectangle()
+            .size([60.0, 20.0])
+            .rotate(std::f32::consts::FRAC_PI_2, [0.5, 0.5]);
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+
+        let rect_id = cx.view_id(&vec![0, 0, 0]);
+        assert_eq!(
+            ui.hittest(&mut path, [30.0, 35.0].into(), &mut cx),
+            Some(rect_id)
+        );
+        assert_eq!(ui.hittest(&mut path, [5.0, 10.0].into(), &mut cx), None);
+
+        // Touches land in the rotated space too.
+        let ui = state(LocalPoint::zero, |s, _| {
+            rectangle()
+                .touch(move |cx, info| cx[s] = info.pt)
+                .size([60.0, 20.0])
+                .rotate(std::f32::consts::FRAC_PI_2, [0.5, 0.5])
+        });
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        cx.process(
+            &ui,
+            &Event::TouchBegin {
+                id: 0,
+                position: [30.0, 35.0].into(),
+            },
+        );
+        let s = StateHandle::<LocalPoint>::new(cx.view_id(&vec![0]));
+        assert!((cx[s] - LocalPoint::new(55.0, 10.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn test_scaled_to_nothing() {
+        let mut cx = Context::new();
+
+        // A view scaled to nothing mid-touch still sees the touch end.
+        let ui = state(
+            || (2.0, None),
+            |s, cx| {
+                let scale = cx[s].0;
+                rectangle()
+                    .touch(move |cx, info| {
+                        if let TouchState::End = info.state {
+                            cx[s].1 = Some(info.pt);
+                        }
+                    })
+                    .size([60.0, 20.0])
+                    .scale(scale, scale, [0.0, 0.0])
+            },
+        );
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        cx.process(
+            &ui,
+            &Event::TouchBegin {
+                id: 0,
+                position: [20.0, 10.0].into(),
+            },
+        );
+        let s = StateHandle::<(f32, Option<LocalPoint>)>::new(cx.view_id(&vec![0]));
+        cx[s].0 = 0.0;
+        cx.process(
+            &ui,
+            &Event::TouchMove {
+                id: 0,
+                position: [30.0, 10.0].into(),
+                delta: [10.0, 0.0].into(),
+            },
+        );
+        assert_eq!(cx[s].1, None);
+        cx.process(
+            &ui,
+            &Event::TouchEnd {
+                id: 0,
+                position: [40.0, 20.0].into(),
+            },
+        );
+        assert_eq!(cx[s].1, Some(LocalPoint::new(20.0, 10.0)));
+        assert_eq!(cx.touches[0], ViewId::default());
+    }
+}
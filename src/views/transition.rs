@@ -0,0 +1,465 @@
+use crate::*;
+use std::any::Any;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+/// A side of a view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// How a view enters when it's added to the tree, and leaves when it's removed.
+///
+/// Effects can be combined, for example
+/// `Transition::opacity().combined(Transition::slide(Edge::Left))`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    opacity: bool,
+    slide: Option<Edge>,
+    scale: bool,
+    animation: Animation,
+}
+
+impl Transition {
+    fn new() -> Self {
+        Self {
+            opacity: false,
+            slide: None,
+            scale: false,
+            animation: Animation::ease_in_out(0.25),
+        }
+    }
+
+    /// Fades the view in and out.
+    pub fn opacity() -> Self {
+        Self {
+            opacity: true,
+            ..Self::new()
+        }
+    }
+
+    /// Moves the view in from `edge` and back out to it, by its own size.
+    pub fn slide(edge: Edge) -> Self {
+        Self {
+            slide: Some(edge),
+            ..Self::new()
+        }
+    }
+
+    /// Grows the view from its center, and shrinks it back.
+    pub fn scale() -> Self {
+        Self {
+            scale: true,
+            ..Self::new()
+        }
+    }
+
+    /// Applies the effects of both transitions, with the animation of this one.
+    pub fn combined(self, other: Transition) -> Self {
+        Self {
+            opacity: self.opacity || other.opacity,
+            slide: self.slide.or(other.slide),
+            scale: self.scale || other.scale,
+            animation: self.animation,
+        }
+    }
+
+    /// Sets the animation, which by default eases in and out over a quarter second.
+    pub fn animation(self, animation: Animation) -> Self {
+        Self { animation, ..self }
+    }
+
+    /// Draws `child` with the effects at `progress`, from 0 when hidden
+    /// to 1 when fully shown. `path` is the path of the transition view.
+    fn draw(&self, progress: f32, child: &dyn DynView, path: &mut IdPath, args: &mut DrawArgs) {
+        let size = args.cx.get_layout(path).rect.size;
+        let hidden = 1.0 - progress;
+
+        args.vger.save();
+        if let Some(edge) = self.slide {
+            let offset = match edge {
+                Edge::Left => [-size.width, 0.0],
+                Edge::Right => [size.width, 0.0],
+                Edge::Top => [0.0, size.height],
+                Edge::Bottom => [0.0, -size.height],
+            };
+            args.vger.translate(LocalOffset::from(offset) * hidden);
+        }
+        if self.scale {
+            let center = LocalOffset::new(size.width, size.height) / 2.0;
+            args.vger.translate(center);
+            args.vger.scale([progress, progress]);
+            args.vger.translate(-center);
+        }
+        let opacity = args.cx.opacity;
+        if self.opacity {
+            args.cx.opacity *= progress;
+        }
+
+        path.push(0);
+        child.draw(path, args);
+        path.pop();
+
+        args.cx.opacity = opacity;
+        args.vger.restore();
+    }
+}
+
+/// Bookkeeping for a view with a transition, kept in the `Context`.
+pub(crate) struct TransitionState {
+    transition: Transition,
+
+    /// 1 when shown, 0 when hidden.
+    progress: Animated<f32>,
+
+    /// The child, as of the last time the tree changed, to keep drawing
+    /// it after it's removed.
+    child: Box<dyn DynView>,
+
+    /// Path of the transition view.
+    path: IdPath,
+
+    /// Transform the view was last drawn with.
+    xform: LocalToWorld,
+
+    /// Has the view been removed from the tree?
+    exiting: bool,
+}
+
+/// Struct for the `transition` modifier.
+#[derive(Clone)]
+pub struct TransitionView<V> {
+    child: V,
+    transition: Transition,
+}
+
+impl<V> TransitionView<V>
+where
+    V: View,
+{
+    pub fn new(child: V, transition: Transition) -> Self {
+        Self { child, transition }
+    }
+}
+
+impl<V> DynView for TransitionView<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let id = args.cx.view_id(path);
+        let progress = match args.cx.transitions.get_mut(&id) {
+            Some(state) => {
+                state.xform = args.vger.current_transform();
+                state.progress.value()
+            }
+            None => 1.0,
+        };
+        self.transition.draw(progress, &self.child, path, args);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let hit_id = self.child.hittest(path, pt, cx);
+        path.pop();
+        hit_id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn focusables(
+        &self,
+        path: &mut IdPath,
+        xform: LocalToWorld,
+        cx: &mut Context,
+        ids: &mut Vec<FocusInfo>,
+    ) {
+        path.push(0);
+        self.child.focusables(path, xform, cx, ids);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        let id = cx.view_id(path);
+        map.push(id);
+
+        // The tree has changed, so update the snapshot drawn after removal.
+        let child = Box::new(self.child.clone());
+        let first_frame = cx.frame <= 1;
+        match cx.transitions.get_mut(&id) {
+            Some(state) => {
+                if state.exiting {
+                    // Added back while leaving.
+                    state.exiting = false;
+                    state.progress.set(1.0, Some(self.transition.animation));
+                }
+                state.transition = self.transition;
+                state.child = child;
+            }
+            None => {
+                // Views in the first frame are already shown.
+                let mut progress = Animated::new(if first_frame { 1.0 } else { 0.0 });
+                progress.set(1.0, Some(self.transition.animation));
+                cx.transitions.insert(
+                    id,
+                    TransitionState {
+                        transition: self.transition,
+                        progress,
+                        child,
+                        path: path.clone(),
+                        xform: LocalToWorld::identity(),
+                        exiting: false,
+                    },
+                );
+            }
+        }
+        if cx.transitions[&id].progress.is_running() {
+            cx.request_frame();
+        }
+
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for TransitionView<V> {}
+
+impl Context {
+    /// Starts the exit of transition views missing from `keep`, the ids of
+    /// the views in the tree, and adds the ids of the views still leaving so
+    /// their state survives until they're gone.
+    pub(crate) fn keep_exiting_transitions(&mut self, keep: &mut Vec<ViewId>) {
+        let live = HashSet::<ViewId>::from_iter(keep.iter().copied());
+        let mut transitions = std::mem::take(&mut self.transitions);
+
+        let removed: Vec<IdPath> = transitions
+            .iter()
+            .filter(|(id, _)| !live.contains(id))
+            .map(|(_, state)| state.path.clone())
+            .collect();
+        transitions.retain(|id, state| {
+            if live.contains(id) {
+                return true;
+            }
+            // Transitions inside a removed subtree leave along with it.
+            let nested = removed
+                .iter()
+                .any(|p| p.len() < state.path.len() && state.path.starts_with(p));
+            if nested {
+                return false;
+            }
+            if !state.exiting {
+                state.exiting = true;
+                state.progress.set(0.0, Some(state.transition.animation));
+            }
+            state.progress.is_running()
+        });
+
+        for (id, state) in &transitions {
+            if state.exiting {
+                keep.push(*id);
+                let mut path = state.path.clone();
+                path.push(0);
+                state.child.gc(&mut path, self, keep);
+            }
+        }
+
+        // Collecting the subtrees ran `gc` on the transition views inside
+        // them, which added themselves to the map taken out above. Putting
+        // ours back discards those, so only the outermost transition of a
+        // leaving subtree runs.
+        self.transitions = transitions;
+    }
+
+    /// Advances the transitions. Finished exits are removed by the next `update`.
+    pub(crate) fn step_transitions(&mut self, dt: f32) {
+        let mut running = false;
+        let mut changed = false;
+        for state in self.transitions.values_mut() {
+            if state.progress.is_running() {
+                changed = true;
+                running |= state.progress.step(dt);
+            }
+        }
+        if changed {
+            self.set_dirty();
+        }
+        if running {
+            self.request_frame();
+        }
+    }
+
+    /// Draws the views which have been removed but are still leaving,
+    /// on top of the rest of the tree. Their parents no longer draw them,
+    /// so they can't be drawn in their place among their siblings.
+    pub(crate) fn draw_exiting_transitions(&mut self, vger: &mut Vger) {
+        let exiting: Vec<_> = self
+            .transitions
+            .values()
+            .filter(|state| state.exiting)
+            .map(|state| {
+                (
+                    state.transition,
+                    state.progress.value(),
+                    state.child.clone(),
+                    state.path.clone(),
+                    state.xform,
+                )
+            })
+            .collect();
+
+        for (transition, progress, child, mut path, xform) in exiting {
+            vger.save();
+            apply_transform(vger, &xform);
+            transition.draw(
+                progress,
+                child.as_ref(),
+                &mut path,
+                &mut DrawArgs { cx: self, vger },
+            );
+            vger.restore();
+        }
+    }
+
+    /// Progress of the transition view at `path`, from 0 when hidden to 1
+    /// when shown, while it's known.
+    #[cfg(test)]
+    fn transition_progress(&mut self, path: &IdPath) -> Option<f32> {
+        let id = self.view_id(path);
+        self.transitions
+            .get(&id)
+            .map(|state| state.progress.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_transition() {
+        let mut cx = Context::new();
+        let clock = VirtualClock::new();
+        cx.set_clock(clock.clone());
+
+        let ui = state(
+            || true,
+            |shown, cx| {
+                cond(
+                    cx[shown],
+                    state(|| 7, |_, _| rectangle())
+                        .transition(Transition::opacity().animation(Animation::ease_in_out(0.5))),
+                    EmptyView {},
+                )
+            },
+        );
+
+        let frame = |cx: &mut Context| {
+            clock.advance(0.1);
+            cx.animate(&ui);
+            if cx.dirty {
+                cx.collect_garbage(&ui);
+                cx.clear_dirty();
+            }
+        };
+
+        let shown = StateHandle::<bool>::new(cx.view_id(&vec![0]));
+        let transition_path = vec![0, 0, 0];
+        let inner = StateHandle::<i32>::new(cx.view_id(&vec![0, 0, 0, 0]));
+
+        // Shown from the start, without an entrance.
+        cx.dirty = true;
+        frame(&mut cx);
+        assert_eq!(cx.transition_progress(&transition_path), Some(1.0));
+        assert_eq!(cx[inner], 7);
+        cx[inner] = 8;
+
+        // Removing it keeps its state until the exit finishes.
+        cx[shown] = false;
+        frame(&mut cx);
+        let mut progress = vec![];
+        for _ in 0..8 {
+            frame(&mut cx);
+            progress.push(cx.transition_progress(&transition_path));
+            if progress.last().unwrap().is_some() {
+                assert_eq!(cx[inner], 8);
+            }
+        }
+        assert!(progress[0].unwrap() < 1.0);
+        assert!(progress.windows(2).all(|w| w[1] <= w[0]));
+        assert_eq!(*progress.last().unwrap(), None);
+        let inner_id = cx.view_id(&vec![0, 0, 0, 0]);
+        assert!(!cx.state_map.contains_key(&inner_id));
+
+        // Adding it back runs the entrance with new state.
+        cx[shown] = true;
+        frame(&mut cx);
+        assert_eq!(cx.transition_progress(&transition_path), Some(0.0));
+        assert!(cx.wants_frame());
+        frame(&mut cx);
+        let entering = cx.transition_progress(&transition_path).unwrap();
+        assert!(entering > 0.0 && entering < 1.0);
+        assert!(cx.wants_frame());
+        assert_eq!(cx[inner], 7);
+        for _ in 0..6 {
+            frame(&mut cx);
+        }
+        assert_eq!(cx.transition_progress(&transition_path), Some(1.0));
+        assert!(!cx.wants_frame());
+    }
+}
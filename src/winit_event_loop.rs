@@ -1,7 +1,7 @@
 use crate::*;
 
 use futures::executor::block_on;
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use std::{collections::VecDeque, sync::Mutex};
 
@@ -10,8 +10,9 @@ use euclid::Point2D;
 use winit::event_loop::EventLoopProxy;
 use winit::{
     application::ApplicationHandler,
+    dpi::{LogicalPosition, LogicalSize},
     event::{
-        DeviceEvent, DeviceId, ElementState as wElementState, KeyEvent as WKeyEvent,
+        DeviceEvent, DeviceId, ElementState as wElementState, Ime, KeyEvent as WKeyEvent,
         MouseButton as WMouseButton, Touch, TouchPhase, WindowEvent,
     },
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
@@ -151,6 +152,33 @@ fn process_event(cx: &mut Context, view: &impl View, event: &Event, window: &Win
     }
 
     cx.prev_grab_cursor = cx.grab_cursor;
+
+    update_ime(cx, window);
+}
+
+/// Enables the IME while a text editor has the focus,
+/// and moves its candidate window to follow the caret.
+fn update_ime(cx: &mut Context, window: &Window) {
+    if cx.ime_rect == cx.prev_ime_rect {
+        return;
+    }
+
+    match cx.ime_rect {
+        Some(rect) => {
+            if cx.prev_ime_rect.is_none() {
+                window.set_ime_allowed(true);
+            }
+            // Window coordinates have y pointing down.
+            let height = window.inner_size().height as f64 / window.scale_factor();
+            window.set_ime_cursor_area(
+                LogicalPosition::new(rect.min_x() as f64, height - rect.max_y() as f64),
+                LogicalSize::new(rect.width() as f64, rect.height() as f64),
+            );
+        }
+        None => window.set_ime_allowed(false),
+    }
+
+    cx.prev_ime_rect = cx.ime_rect;
 }
 
 struct EventHandler<T>
@@ -169,11 +197,48 @@ where
     // We track all the mouse movement events to make this easier to access
     // by event handlers.
     mouse_position: Point2D<f32, LocalSpace>,
+    // Time and position of the last mouse press, for counting double-clicks.
+    #[cfg(not(target_arch = "wasm32"))]
+    last_click: Option<(std::time::Instant, LocalPoint)>,
     cx: Context,
     view: T,
     access_nodes: Vec<(accesskit::NodeId, accesskit::Node)>,
 }
 
+impl<T> EventHandler<T>
+where
+    T: View,
+{
+    /// Counts clicks which are close together in time and space.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn count_click(&mut self) -> u32 {
+        const DOUBLE_CLICK_TIME: std::time::Duration = std::time::Duration::from_millis(500);
+        const DOUBLE_CLICK_DISTANCE: f32 = 4.0;
+
+        let now = std::time::Instant::now();
+        let repeated = match self.last_click {
+            Some((time, position)) => {
+                now.duration_since(time) < DOUBLE_CLICK_TIME
+                    && position.distance_to(self.mouse_position) < DOUBLE_CLICK_DISTANCE
+            }
+            None => false,
+        };
+        self.last_click = Some((now, self.mouse_position));
+
+        if repeated {
+            self.cx.click_count + 1
+        } else {
+            1
+        }
+    }
+
+    // std::time::Instant isn't available on the web.
+    #[cfg(target_arch = "wasm32")]
+    fn count_click(&mut self) -> u32 {
+        1
+    }
+}
+
 impl<T> ApplicationHandler for EventHandler<T>
 where
     T: View,
@@ -260,6 +325,8 @@ where
                         [width, height].into(),
                         scale,
                     );
+
+                    update_ime(&mut self.cx, window);
                 }
             }
             WindowEvent::MouseInput { state, button, .. } => {
@@ -279,6 +346,7 @@ where
                             _ => (),
                         };
 
+                        self.cx.click_count = self.count_click();
                         self.cx.set_dirty();
 
                         if let Some(window) = &self.window {
@@ -395,13 +463,9 @@ where
                     keyboard::Key::Named(keyboard::NamedKey::F10) => Some(Key::F10),
                     keyboard::Key::Named(keyboard::NamedKey::F11) => Some(Key::F11),
                     keyboard::Key::Named(keyboard::NamedKey::F12) => Some(Key::F12),
-                    keyboard::Key::Character(str) => {
-                        if let Some(c) = str.chars().next() {
-                            Some(Key::Character(c))
-                        } else {
-                            None
-                        }
-                    }
+                    // Key events identify the key for shortcuts. The full text
+                    // is sent separately as Event::TextInput.
+                    keyboard::Key::Character(str) => str.chars().next().map(Key::Character),
                     _ => None,
                 };
 
@@ -409,9 +473,50 @@ where
                     self.cx.process(&self.view, &Event::Key(key))
                 }
 
+                // Shortcuts don't type text. Windows reports AltGr as
+                // Control with Alt, which does.
+                let mods = self.cx.key_mods;
+                let shortcut = (mods.control && !mods.alt) || mods.command;
+                if key_event.state == wElementState::Pressed && !shortcut {
+                    // Control characters such as backspace are handled as keys.
+                    if let Some(text) = key_event
+                        .text
+                        .as_ref()
+                        .filter(|t| !t.is_empty() && !t.chars().any(|c| c.is_control()))
+                    {
+                        self.cx
+                            .process(&self.view, &Event::TextInput(text.to_string()))
+                    }
+                }
+
                 if let (Some(key), wElementState::Released) = (key, key_event.state) {
                     self.cx.process(&self.view, &Event::KeyReleased(key))
                 }
+
+                // Tab and Escape can move the focus away from a text editor.
+                if let Some(window) = &self.window {
+                    update_ime(&mut self.cx, window);
+                }
+            }
+
+            WindowEvent::Ime(ime) => {
+                let event = match ime {
+                    Ime::Preedit(text, cursor) => Some(Event::ImePreedit { text, cursor }),
+                    Ime::Commit(text) => Some(Event::ImeCommit(text)),
+                    // Clear any composition in progress.
+                    Ime::Disabled => Some(Event::ImePreedit {
+                        text: String::new(),
+                        cursor: None,
+                    }),
+                    Ime::Enabled => None,
+                };
+
+                if let Some(event) = event {
+                    self.cx.process(&self.view, &event);
+                    if let Some(window) = &self.window {
+                        update_ime(&mut self.cx, window);
+                    }
+                }
             }
 
             WindowEvent::ModifiersChanged(mods) => {
@@ -477,12 +582,14 @@ where
             let width = window_size.width as f32 / scale;
             let height = window_size.height as f32 / scale;
 
+            // Keep drawing frames while animations are running.
             if self.cx.update(
                 &self.view,
                 &mut context.vger,
                 &mut self.access_nodes,
                 [width, height].into(),
-            ) {
+            ) || self.cx.wants_frame()
+            {
                 window.request_redraw();
             }
 
@@ -505,6 +612,8 @@ pub fn rui(view: impl View) {
         context: None,
         window: None,
         mouse_position: LocalPoint::zero(),
+        #[cfg(not(target_arch = "wasm32"))]
+        last_click: None,
         cx: Context::new(),
         view,
         access_nodes: vec![],
@@ -516,12 +625,10 @@ pub fn rui(view: impl View) {
     }
 
     let mut commands: Vec<CommandInfo> = Vec::new();
-    let mut command_map = HashMap::new();
     app.cx.commands(&app.view, &mut commands);
 
-    {
-        // So we can infer a type for CommandMap when winit is enabled.
-        command_map.insert("", "");
+    for (key, a, b) in hotkey_conflicts(&commands) {
+        log::warn!("hotkey {:?} is bound to both {:?} and {:?}", key, a, b);
     }
 
     if let Err(e) = event_loop.run_app(&mut app) {